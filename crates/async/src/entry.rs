@@ -6,17 +6,22 @@
 
 use crate::*;
 use futures_util::{
-    future::Future,
+    future::{join_all, Future},
+    stream::{self, Stream},
     task::{AtomicWaker, Context, Poll},
 };
 use plctag_core::ffi::PLCTAG_ERR_NOT_FOUND;
 #[cfg(feature = "value")]
 use plctag_core::{Decode, Encode};
+#[cfg(feature = "tokio")]
+use std::time::Duration;
 use std::{
     ffi::c_void,
     pin::Pin,
     sync::atomic::{AtomicBool, AtomicI32, AtomicU8, Ordering},
 };
+#[cfg(feature = "tokio")]
+use tokio::time::MissedTickBehavior;
 
 const PLCTAG_EVENT_CREATED: i32 = plctag_core::ffi::PLCTAG_EVENT_CREATED as i32;
 const PLCTAG_EVENT_READ_COMPLETED: i32 = plctag_core::ffi::PLCTAG_EVENT_READ_COMPLETED as i32;
@@ -40,6 +45,8 @@ struct Inner {
     set: AtomicBool,
     event: AtomicI32,
     status: AtomicI32,
+    /// `read()` or `write()` not completed yet
+    inflight: AtomicBool,
 }
 
 impl Inner {
@@ -50,6 +57,7 @@ impl Inner {
             set: AtomicBool::new(false),
             event: AtomicI32::new(0),
             status: AtomicI32::new(0),
+            inflight: AtomicBool::new(false),
         }
     }
 
@@ -76,6 +84,9 @@ impl Inner {
             PLCTAG_EVENT_DESTROYED => {
                 self.state.store(TAG_DESTROYED, Ordering::Relaxed);
             }
+            PLCTAG_EVENT_READ_COMPLETED | PLCTAG_EVENT_WRITE_COMPLETED => {
+                self.inflight.store(false, Ordering::Relaxed);
+            }
             _ => {}
         }
         self.event.store(event, Ordering::Relaxed);
@@ -112,20 +123,6 @@ impl AsyncTag {
     /// # Tag String Attributes
     /// See https://github.com/libplctag/libplctag/wiki/Tag-String-Attributes for tag string attributes.
     pub fn new<P: Into<Vec<u8>>>(path: P) -> Result<Self> {
-        extern "C" fn on_event(_tag: i32, event: i32, status: i32, user_data: *mut c_void) {
-            match event {
-                PLCTAG_EVENT_CREATED
-                | PLCTAG_EVENT_DESTROYED
-                | PLCTAG_EVENT_READ_COMPLETED
-                | PLCTAG_EVENT_WRITE_COMPLETED => unsafe {
-                    let ptr = user_data as *const Inner;
-                    let inner = &*ptr;
-                    inner.set_event(event, status);
-                },
-                _ => {}
-            }
-        }
-
         let inner = Arc::new(Inner::new());
         let tag = {
             let user_data = Arc::as_ptr(&inner) as *mut Inner as *mut c_void;
@@ -138,6 +135,22 @@ impl AsyncTag {
         Ok(Self { tag, inner })
     }
 
+    /// create instance of [`AsyncTag`] from existing [`RawTag`], i.e. created by blocking API.
+    /// see [`AsyncTag::into_raw`] for the reverse.
+    ///
+    /// # Note
+    /// it overrides the callback registered on the tag.
+    /// the tag is treated as created unless it's pending, which might be still creating.
+    pub fn from_raw(tag: RawTag) -> Result<Self> {
+        let inner = Arc::new(Inner::new());
+        let user_data = Arc::as_ptr(&inner) as *mut Inner as *mut c_void;
+        unsafe { tag.register_callback_ex(Some(on_event), user_data) }.into_result()?;
+        if !tag.status().is_pending() {
+            inner.state.store(TAG_CREATED, Ordering::Release);
+        }
+        Ok(Self { tag, inner })
+    }
+
     /// create instance of [`AsyncTag`]
     ///
     /// # Tag String Attributes
@@ -188,6 +201,17 @@ impl AsyncTag {
         }
     }
 
+    /// wait until created, it's [`Error::Timeout`] if not created in time.
+    /// i.e. fail fast at startup if the PLC is unreachable
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub async fn ready_timeout(&mut self, timeout: Duration) -> Result<()> {
+        match tokio::time::timeout(timeout, self.ready()).await {
+            Ok(res) => res,
+            Err(_) => Err(Error::Timeout),
+        }
+    }
+
     /// perform read operation.
     #[inline]
     pub async fn read(&mut self) -> Result<()> {
@@ -203,8 +227,10 @@ impl AsyncTag {
     #[inline]
     async fn read_or_write(&mut self, event: i32) -> Result<()> {
         self.ready().await?;
+        self.inner.inflight.store(true, Ordering::Release);
         let mut guard = InflightGuard {
             tag: &self.tag,
+            inner: &self.inner,
             pending: true,
         };
         match event {
@@ -228,11 +254,108 @@ impl AsyncTag {
         Ok(())
     }
 
-    /// poll status
+    /// stream of read completions, i.e. periodic reads when `auto_sync_read_ms` is configured.
+    ///
+    /// the stream ends after yielding the error once the tag is destroyed.
+    ///
+    /// # Note
+    /// do not perform other operations on the tag while polling the stream,
+    /// they share the same event.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use plctag_async::AsyncTag;
+    ///
+    /// # async fn demo() {
+    /// let path="protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyTag1&elem_count=1&elem_size=16&auto_sync_read_ms=200";
+    /// let tag = AsyncTag::new(path).unwrap();
+    /// let updates = tag.subscribe_updates();
+    /// futures_util::pin_mut!(updates);
+    /// while let Some(Ok(())) = updates.next().await {
+    ///     // new data arrived
+    /// }
+    /// # }
+    /// ```
+    pub fn subscribe_updates(&self) -> impl Stream<Item = Result<()>> + '_ {
+        stream::unfold(Some(self), |tag| async move {
+            let tag = tag?;
+            let status = tag.recv_event(PLCTAG_EVENT_READ_COMPLETED).await;
+            let res = status.into_result().map_err(Error::from);
+            let next = if tag.inner.state() == TAG_DESTROYED {
+                None
+            } else {
+                Some(tag)
+            };
+            Some((res, next))
+        })
+    }
+
+    /// stream of reads issued every `interval`, yields the read results.
+    ///
+    /// ticks are skipped if the previous read is still pending, the stream ends after yielding
+    /// the error once the tag is destroyed.
+    ///
+    /// # Panics
+    /// panics if `interval` is zero
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use plctag_async::AsyncTag;
+    /// use std::time::Duration;
+    ///
+    /// # async fn demo() {
+    /// let path="protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyTag1&elem_count=1&elem_size=16";
+    /// let mut tag = AsyncTag::new(path).unwrap();
+    /// let reads = tag.poll_stream(Duration::from_millis(200));
+    /// futures_util::pin_mut!(reads);
+    /// while let Some(Ok(())) = reads.next().await {
+    ///     // new data arrived
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn poll_stream(&mut self, interval: Duration) -> impl Stream<Item = Result<()>> + '_ {
+        assert!(!interval.is_zero(), "interval must be non-zero");
+        stream::unfold(Some((self, None)), move |state| async move {
+            let (tag, ticker) = state?;
+            // timers require the runtime, create on first poll
+            let mut ticker = ticker.unwrap_or_else(|| {
+                let mut ticker = tokio::time::interval(interval);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+                ticker
+            });
+            ticker.tick().await;
+            let res = tag.read().await;
+            let next = if tag.inner.state() == TAG_DESTROYED {
+                None
+            } else {
+                Some((tag, Some(ticker)))
+            };
+            Some((res, next))
+        })
+    }
+
+    /// abort the pending operation, without dropping the tag.
+    ///
+    /// # Note
+    /// dropping a pending `read()` or `write()` future also aborts the operation
+    #[inline]
+    pub fn abort(&mut self) -> Result<()> {
+        self.tag.abort()?;
+        // discard event of the aborted operation
+        let _ = self.inner.take_event();
+        self.inner.inflight.store(false, Ordering::Release);
+        Ok(())
+    }
+
+    /// poll status, pending until the completion of `read()` or `write()` is received
     #[inline]
     pub fn status(&mut self) -> Status {
         match self.inner.state() {
             TAG_DESTROYED => Status::Err(PLCTAG_ERR_NOT_FOUND),
+            _ if self.inner.inflight.load(Ordering::Acquire) => Status::Pending,
             _ => self.tag.status(),
         }
     }
@@ -322,6 +445,27 @@ impl AsyncTag {
         Ok(self.tag.get_value(offset)?)
     }
 
+    /// perform read & returns the value, returns [`Error::Timeout`] if not done in time.
+    ///
+    /// the pending read is aborted on timeout.
+    #[cfg(all(feature = "value", feature = "tokio"))]
+    #[inline]
+    pub async fn read_value_timeout<T: Decode>(
+        &mut self,
+        offset: u32,
+        timeout: Duration,
+    ) -> Result<T> {
+        match tokio::time::timeout(timeout, self.read()).await {
+            Ok(res) => res?,
+            Err(_) => {
+                // read future dropped & aborted, discard event of the aborted operation
+                let _ = self.inner.take_event();
+                return Err(Error::Timeout);
+            }
+        }
+        self.get_value(offset)
+    }
+
     /// perform read & returns the value
     #[cfg(feature = "value")]
     #[inline]
@@ -375,6 +519,19 @@ impl AsyncTag {
         Ok(self.tag.set_bytes_unchecked(byte_offset, buf)?)
     }
 
+    /// the underlying `libplctag` tag id, see [`RawTag::id`]
+    #[inline(always)]
+    pub fn id(&self) -> i32 {
+        self.tag.id()
+    }
+
+    /// create an independent [`AsyncTag`] with the same path, see [`RawTag::try_clone`]
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub(crate) fn try_clone(&self) -> Result<Self> {
+        Self::from_raw(self.tag.try_clone(0)?)
+    }
+
     /// take the inner
     pub fn into_raw(self) -> RawTag {
         self.tag.unregister_callback();
@@ -382,8 +539,46 @@ impl AsyncTag {
     }
 }
 
+extern "C" fn on_event(_tag: i32, event: i32, status: i32, user_data: *mut c_void) {
+    match event {
+        PLCTAG_EVENT_CREATED
+        | PLCTAG_EVENT_DESTROYED
+        | PLCTAG_EVENT_READ_COMPLETED
+        | PLCTAG_EVENT_WRITE_COMPLETED => unsafe {
+            let ptr = user_data as *const Inner;
+            let inner = &*ptr;
+            inner.set_event(event, status);
+        },
+        _ => {}
+    }
+}
+
+/// perform read operations of all tags concurrently.
+/// results are in the same order as the input tags.
+///
+/// # Examples
+/// ```rust,no_run
+/// use plctag_async::{read_all, AsyncTag};
+///
+/// # async fn demo() {
+/// let path1="protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyTag1&elem_count=1&elem_size=16";
+/// let path2="protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyTag2&elem_count=1&elem_size=16";
+/// let mut tag1 = AsyncTag::new(path1).unwrap();
+/// let mut tag2 = AsyncTag::new(path2).unwrap();
+/// let results = read_all([&mut tag1, &mut tag2]).await;
+/// assert!(results.iter().all(|r| r.is_ok()));
+/// # }
+/// ```
+pub async fn read_all<'a, I>(tags: I) -> Vec<Result<()>>
+where
+    I: IntoIterator<Item = &'a mut AsyncTag>,
+{
+    join_all(tags.into_iter().map(|tag| tag.read())).await
+}
+
 struct InflightGuard<'a> {
     tag: &'a RawTag,
+    inner: &'a Inner,
     pending: bool,
 }
 
@@ -400,7 +595,11 @@ impl InflightGuard<'_> {
                 self.pending = false;
                 Err(status.into())
             }
-            _ => unreachable!(),
+            // completed synchronously, i.e. system tags
+            Status::Ok => {
+                self.pending = false;
+                Ok(())
+            }
         }
     }
 
@@ -416,7 +615,11 @@ impl InflightGuard<'_> {
                 self.pending = false;
                 Err(status.into())
             }
-            _ => unreachable!(),
+            // completed synchronously, i.e. system tags
+            Status::Ok => {
+                self.pending = false;
+                Ok(())
+            }
         }
     }
 }
@@ -427,5 +630,167 @@ impl Drop for InflightGuard<'_> {
         if self.pending {
             let _ = self.tag.abort();
         }
+        self.inner.inflight.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const DEBUG_TAG: &str = "make=system&family=library&name=debug&debug=4";
+
+    /// deliver event to the tag like the library does
+    fn deliver(inner: &Arc<Inner>, event: i32, status: i32) {
+        let user_data = Arc::as_ptr(inner) as *mut Inner as *mut c_void;
+        on_event(0, event, status, user_data);
+    }
+
+    /// tag that never gets ready, events are not delivered
+    fn pending_tag() -> AsyncTag {
+        let tag = RawTag::new(DEBUG_TAG, 100).unwrap();
+        AsyncTag {
+            tag,
+            inner: Arc::new(Inner::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_all() {
+        let mut tag1 = AsyncTag::new(DEBUG_TAG).unwrap();
+        let mut tag2 = AsyncTag::new(DEBUG_TAG).unwrap();
+        let results = read_all([&mut tag1, &mut tag2]).await;
+        assert_eq!(results.len(), 2);
+        for res in results {
+            assert!(res.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_abort() {
+        let mut tag = pending_tag();
+        let inner = tag.inner.clone();
+        deliver(&inner, PLCTAG_EVENT_CREATED, 0);
+
+        // the completion never arrives, keep the read in flight without the guard aborting it
+        let mut fut = Box::pin(tag.read());
+        assert!(futures_util::poll!(fut.as_mut()).is_pending());
+        std::mem::forget(fut);
+        assert!(tag.status().is_pending());
+
+        tag.abort().unwrap();
+        assert!(!tag.status().is_pending());
+
+        let mut fut = Box::pin(tag.read());
+        assert!(futures_util::poll!(fut.as_mut()).is_pending());
+        deliver(&inner, PLCTAG_EVENT_READ_COMPLETED, 0);
+        fut.await.unwrap();
+        assert!(tag.status().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_drop_pending_read() {
+        let mut tag = AsyncTag::new(DEBUG_TAG).unwrap();
+        tag.ready().await.unwrap();
+        {
+            let fut = tag.read();
+            futures_util::pin_mut!(fut);
+            // start the read, then drop it before completion
+            let _ = futures_util::poll!(fut.as_mut());
+        }
+        tag.read().await.unwrap();
+    }
+
+    #[cfg(all(feature = "value", feature = "tokio"))]
+    #[tokio::test]
+    async fn test_read_value_timeout() {
+        let mut tag = pending_tag();
+        let res: Result<u16> = tag.read_value_timeout(0, Duration::from_millis(100)).await;
+        assert!(matches!(res, Err(Error::Timeout)));
+
+        let mut tag = AsyncTag::new(DEBUG_TAG).unwrap();
+        let res: Result<u8> = tag.read_value_timeout(0, Duration::from_secs(5)).await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_updates() {
+        use futures_util::StreamExt;
+
+        let path = format!("{}&auto_sync_read_ms=50", DEBUG_TAG);
+        let mut tag = AsyncTag::new(path).unwrap();
+        tag.ready().await.unwrap();
+        let updates = tag.subscribe_updates();
+        futures_util::pin_mut!(updates);
+        let item = tokio::time::timeout(Duration::from_secs(5), updates.next())
+            .await
+            .unwrap();
+        assert!(matches!(item, Some(Ok(()))));
+    }
+
+    #[cfg(feature = "value")]
+    #[tokio::test]
+    async fn test_read_value_in_place() {
+        let mut tag = AsyncTag::new(DEBUG_TAG).unwrap();
+        let value: [u8; 4] = tag.read_value(0).await.unwrap();
+        let mut place = [0xff_u8; 4];
+        tag.read_value_in_place(0, &mut place).await.unwrap();
+        assert_eq!(place, value);
+
+        let mut place = [0xff_u8; 4];
+        tag.get_value_in_place(0, &mut place).unwrap();
+        assert_eq!(place, value);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_poll_stream() {
+        use futures_util::StreamExt;
+
+        let interval = Duration::from_millis(50);
+        let mut tag = AsyncTag::new(DEBUG_TAG).unwrap();
+        tag.ready().await.unwrap();
+        let reads = tag.poll_stream(interval).take(2).collect::<Vec<_>>();
+        let items = tokio::time::timeout(interval * 3, reads).await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(|v| v.is_ok()));
+    }
+
+    #[cfg(feature = "builder")]
+    #[test]
+    fn test_builder_error() {
+        use plctag_core::builder::PathBuilder;
+
+        fn create() -> Result<AsyncTag> {
+            let path = PathBuilder::default().name("MyTag1").build()?;
+            AsyncTag::new(path)
+        }
+        let err = create().unwrap_err();
+        assert!(matches!(err, Error::Other(_)));
+        assert_eq!(err.to_string(), "protocol required");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_ready_timeout() {
+        let mut tag = pending_tag();
+        let res = tag.ready_timeout(Duration::from_millis(100)).await;
+        assert!(matches!(res, Err(Error::Timeout)));
+
+        let mut tag = AsyncTag::new(DEBUG_TAG).unwrap();
+        let res = tag.ready_timeout(Duration::from_secs(5)).await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_from_raw() {
+        let raw = RawTag::new(DEBUG_TAG, 100).unwrap();
+        let mut tag = AsyncTag::from_raw(raw).unwrap();
+        tag.ready().await.unwrap();
+        tag.read().await.unwrap();
+
+        let raw = tag.into_raw();
+        assert!(raw.read(100).is_ok());
     }
 }
@@ -23,6 +23,14 @@ Add `plctag-async` to your Cargo.toml
 plctag-async= "0.4"
 ```
 
+## Features
+
+- `value`: read & write values with `Decode`/`Encode`, enabled by default
+- `builder`: convert `builder::Error` into [`Error`]
+- `tokio`: timeouts, polling and reconnecting, which require tokio timers:
+  `AsyncTag::ready_timeout`, `AsyncTag::read_value_timeout`, `AsyncTag::poll_stream`
+  and `ReconnectingTag`
+
 ## Examples
 
 ```rust,no_run
@@ -52,8 +60,12 @@ MIT
 
 extern crate plctag_core;
 mod entry;
+#[cfg(feature = "tokio")]
+mod reconnect;
 
-pub use entry::AsyncTag;
+pub use entry::{read_all, AsyncTag};
+#[cfg(feature = "tokio")]
+pub use reconnect::ReconnectingTag;
 
 use plctag_core::{RawTag, Status};
 use std::{fmt, sync::Arc};
@@ -66,6 +78,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     /// plc tag error
     TagError(Status),
+    /// operation timed out, the pending operation was aborted
+    Timeout,
     /// other error
     Other(Box<dyn std::error::Error + Send + Sync + 'static>),
 }
@@ -73,7 +87,7 @@ pub enum Error {
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Error::TagError(_) => None,
+            Error::TagError(_) | Error::Timeout => None,
             Error::Other(e) => Some(e.as_ref()),
         }
     }
@@ -83,6 +97,7 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::TagError(e) => write!(f, "TagError - {}", e),
+            Error::Timeout => write!(f, "Timeout"),
             Error::Other(e) => write!(f, "{}", e),
         }
     }
@@ -93,3 +108,10 @@ impl From<Status> for Error {
         Error::TagError(s)
     }
 }
+
+#[cfg(feature = "builder")]
+impl From<plctag_core::builder::Error> for Error {
+    fn from(e: plctag_core::builder::Error) -> Self {
+        Error::Other(Box::new(e))
+    }
+}
@@ -0,0 +1,226 @@
+// plctag-rs
+//
+// a rust wrapper of libplctag, with rust style APIs and useful extensions.
+// Copyright: 2022, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use crate::*;
+use plctag_core::{ffi::PLCTAG_ERR_NOT_FOUND, ConnectionStatus};
+#[cfg(feature = "value")]
+use plctag_core::{Decode, Encode};
+use std::time::Duration;
+
+/// wrapper of [`AsyncTag`], recreates the tag from the path of the inner tag when the tag was
+/// destroyed or the connection was lost, then retries the operation.
+///
+/// # Examples
+/// ```rust,no_run
+/// use plctag_async::ReconnectingTag;
+/// use std::time::Duration;
+///
+/// # async fn demo() {
+/// let path="protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyTag1&elem_count=1&elem_size=16";
+/// let mut tag = ReconnectingTag::new(path).unwrap();
+/// tag.max_retries(5).backoff(Duration::from_millis(200));
+/// let value: u16 = tag.read_value(0).await.unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReconnectingTag {
+    tag: AsyncTag,
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl ReconnectingTag {
+    /// create instance of [`ReconnectingTag`], retries 3 times with backoff 100ms by default
+    ///
+    /// # Tag String Attributes
+    /// See https://github.com/libplctag/libplctag/wiki/Tag-String-Attributes for tag string attributes.
+    pub fn new<P: Into<Vec<u8>>>(path: P) -> Result<Self> {
+        let tag = AsyncTag::new(path)?;
+        Ok(Self {
+            tag,
+            max_retries: 3,
+            backoff: Duration::from_millis(100),
+        })
+    }
+
+    /// max retries before giving up
+    #[inline]
+    pub fn max_retries(&mut self, max_retries: u32) -> &mut Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// initial delay before recreating the tag, doubled for every retry
+    #[inline]
+    pub fn backoff(&mut self, backoff: Duration) -> &mut Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// the inner tag
+    #[inline]
+    pub fn tag(&mut self) -> &mut AsyncTag {
+        &mut self.tag
+    }
+
+    /// perform read operation, recreate the tag and retry if necessary
+    pub async fn read(&mut self) -> Result<()> {
+        let mut retries = 0;
+        loop {
+            match self.tag.read().await {
+                Err(Error::TagError(status))
+                    if should_reconnect(status) && retries < self.max_retries =>
+                {
+                    self.reconnect(retries).await?;
+                    retries += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// perform write operation, recreate the tag and retry if necessary.
+    ///
+    /// the tag data buffer is copied to the recreated tag before retrying.
+    /// if the tag was destroyed before the call, the data set on it is lost, the error is returned
+    /// without recreating the tag, use [`ReconnectingTag::write_value`] instead.
+    pub async fn write(&mut self) -> Result<()> {
+        self.tag.ready().await?;
+        let mut buf = vec![0; self.tag.size()? as usize];
+        let len = self.tag.get_bytes(0, &mut buf)?;
+        buf.truncate(len);
+
+        let mut retries = 0;
+        loop {
+            match self.tag.write().await {
+                Err(Error::TagError(status))
+                    if should_reconnect(status) && retries < self.max_retries =>
+                {
+                    self.reconnect(retries).await?;
+                    retries += 1;
+                    self.tag.ready().await?;
+                    self.tag.set_bytes_unchecked(0, &buf)?;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// perform read & returns the value
+    #[cfg(feature = "value")]
+    #[inline]
+    pub async fn read_value<T: Decode>(&mut self, offset: u32) -> Result<T> {
+        self.read().await?;
+        self.tag.get_value(offset)
+    }
+
+    /// set the value and write to PLC Controller, recreate the tag and retry if necessary
+    #[cfg(feature = "value")]
+    #[inline]
+    pub async fn write_value<T: Encode + Send>(&mut self, offset: u32, value: T) -> Result<()> {
+        self.ready().await?;
+        self.tag.set_value(offset, value)?;
+        self.write().await
+    }
+
+    /// wait until created, recreate the tag and retry if necessary
+    #[cfg(feature = "value")]
+    async fn ready(&mut self) -> Result<()> {
+        let mut retries = 0;
+        loop {
+            match self.tag.ready().await {
+                Err(Error::TagError(status))
+                    if should_reconnect(status) && retries < self.max_retries =>
+                {
+                    self.reconnect(retries).await?;
+                    retries += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// wait for backoff, then recreate the tag
+    async fn reconnect(&mut self, retries: u32) -> Result<()> {
+        let delay = self.backoff.saturating_mul(1 << retries.min(16));
+        tokio::time::sleep(delay).await;
+        self.tag = self.tag.try_clone()?;
+        Ok(())
+    }
+}
+
+#[inline]
+fn should_reconnect(status: Status) -> bool {
+    status.code() == PLCTAG_ERR_NOT_FOUND
+        || matches!(
+            ConnectionStatus::from(status),
+            ConnectionStatus::Disconnected(_)
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEBUG_TAG: &str = "make=system&family=library&name=debug&debug=4";
+
+    /// destroy the inner tag, the callback receives the destroyed event
+    fn destroy(tag: &mut ReconnectingTag) {
+        let rc = unsafe { plctag_core::ffi::plc_tag_destroy(tag.tag().id()) };
+        assert_eq!(rc, plctag_core::ffi::PLCTAG_STATUS_OK as i32);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect() {
+        let mut tag = ReconnectingTag::new(DEBUG_TAG).unwrap();
+        tag.backoff(Duration::from_millis(10));
+        tag.read().await.unwrap();
+
+        destroy(&mut tag);
+        assert!(tag.tag().status().is_err());
+        tag.read().await.unwrap();
+        assert!(tag.tag().status().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_write_destroyed() {
+        let mut tag = ReconnectingTag::new(DEBUG_TAG).unwrap();
+        tag.backoff(Duration::from_millis(10));
+        tag.tag().ready().await.unwrap();
+        let id = tag.tag().id();
+        destroy(&mut tag);
+
+        // data set on the destroyed tag is lost, never write the buffer of a recreated tag
+        let res = tag.write().await;
+        assert!(matches!(res, Err(Error::TagError(s)) if s.code() == PLCTAG_ERR_NOT_FOUND));
+        assert_eq!(tag.tag().id(), id);
+    }
+
+    #[cfg(feature = "value")]
+    #[tokio::test]
+    async fn test_write_value_destroyed() {
+        let mut tag = ReconnectingTag::new(DEBUG_TAG).unwrap();
+        tag.backoff(Duration::from_millis(10));
+        tag.tag().ready().await.unwrap();
+        destroy(&mut tag);
+
+        // the debug level of the tag path, writing it does not affect other tests
+        tag.write_value(0, 4_u32).await.unwrap();
+        assert!(tag.tag().status().is_ok());
+        let value: u32 = tag.tag().get_value(0).unwrap();
+        assert_eq!(value, 4);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_max_retries() {
+        let mut tag = ReconnectingTag::new(DEBUG_TAG).unwrap();
+        tag.max_retries(0);
+        tag.tag().ready().await.unwrap();
+        destroy(&mut tag);
+        let res = tag.read().await;
+        assert!(matches!(res, Err(Error::TagError(s)) if s.code() == PLCTAG_ERR_NOT_FOUND));
+    }
+}
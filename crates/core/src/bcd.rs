@@ -0,0 +1,100 @@
+// plctag-rs
+//
+// a rust wrapper of libplctag, with rust style APIs and useful extensions.
+// Copyright: 2022, Joylei <leingliu@gmail.com>
+// License: MIT
+
+//! helpers for binary-coded decimals, i.e. BCD values of PLC5/SLC tags
+//!
+//! each decimal digit takes a nibble of the `u32`, the least significant digit in the lowest
+//! nibble, so up to 8 digits are stored: `1234` is stored as `0x0000_1234`.
+//!
+//! # Examples
+//! ```rust,no_run
+//! use plctag_core::{bcd, RawTag};
+//!
+//! let path = "protocol=ab-eip&plc=slc500&gateway=192.168.1.120&name=N7:0&elem_size=4";
+//! let tag = RawTag::new(path, 100).unwrap();
+//! tag.read(100).into_result().unwrap();
+//! let value = bcd::get_bcd_u32(&tag, 0).unwrap();
+//! ```
+
+use crate::{ffi, RawTag, Result, Status};
+
+/// max value that fits in 8 BCD digits
+pub const MAX_BCD_U32: u32 = 99_999_999;
+
+/// get `u32` value stored as BCD; nibbles greater than 9 are `PLCTAG_ERR_BAD_DATA`
+#[inline]
+pub fn get_bcd_u32(tag: &RawTag, byte_offset: u32) -> Result<u32> {
+    let raw = tag.get_u32(byte_offset)?;
+    from_bcd(raw).ok_or(Status::Err(ffi::PLCTAG_ERR_BAD_DATA))
+}
+
+/// set `u32` value as BCD; values greater than [`MAX_BCD_U32`] are `PLCTAG_ERR_TOO_LARGE`
+#[inline]
+pub fn set_bcd_u32(tag: &RawTag, byte_offset: u32, value: u32) -> Result<()> {
+    let raw = to_bcd(value).ok_or(Status::Err(ffi::PLCTAG_ERR_TOO_LARGE))?;
+    tag.set_u32(byte_offset, raw)
+}
+
+/// convert BCD to binary, `None` if any nibble is greater than 9
+#[inline]
+pub fn from_bcd(raw: u32) -> Option<u32> {
+    let mut value = 0;
+    for i in (0..8).rev() {
+        let digit = (raw >> (i * 4)) & 0xF;
+        if digit > 9 {
+            return None;
+        }
+        value = value * 10 + digit;
+    }
+    Some(value)
+}
+
+/// convert binary to BCD, `None` if greater than [`MAX_BCD_U32`]
+#[inline]
+pub fn to_bcd(mut value: u32) -> Option<u32> {
+    if value > MAX_BCD_U32 {
+        return None;
+    }
+    let mut raw = 0;
+    let mut shift = 0;
+    while value > 0 {
+        raw |= (value % 10) << shift;
+        value /= 10;
+        shift += 4;
+    }
+    Some(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(to_bcd(1234), Some(0x1234));
+        assert_eq!(from_bcd(0x1234), Some(1234));
+        assert_eq!(to_bcd(0), Some(0));
+        assert_eq!(to_bcd(MAX_BCD_U32), Some(0x9999_9999));
+        assert_eq!(from_bcd(0x9999_9999), Some(MAX_BCD_U32));
+        assert_eq!(to_bcd(MAX_BCD_U32 + 1), None);
+        assert_eq!(from_bcd(0x12A4), None);
+    }
+
+    #[test]
+    fn test_bcd_tag() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        set_bcd_u32(&tag, 0, 1234).unwrap();
+        assert_eq!(tag.get_u32(0).unwrap(), 0x1234);
+        assert_eq!(get_bcd_u32(&tag, 0).unwrap(), 1234);
+
+        let res = set_bcd_u32(&tag, 0, 100_000_000);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_TOO_LARGE);
+
+        tag.set_u32(0, 0xFF).unwrap();
+        let res = get_bcd_u32(&tag, 0);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_DATA);
+    }
+}
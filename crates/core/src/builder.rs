@@ -9,9 +9,15 @@
 pub use crate::debug::DebugLevel;
 use core::fmt;
 use core::fmt::Write;
+use core::str::FromStr;
 
 type Result<T> = std::result::Result<T, Error>;
 
+/// max coils or discrete inputs per Modbus request
+const MODBUS_MAX_BITS: usize = 2000;
+/// max bytes of holding or input registers per Modbus request, 125 registers
+const MODBUS_MAX_BYTES: usize = 250;
+
 /// tag builder error
 #[derive(Debug)]
 pub struct Error(&'static str);
@@ -24,13 +30,24 @@ impl fmt::Display for Error {
 
 impl std::error::Error for Error {}
 
+/// builder errors are `PLCTAG_ERR_BAD_PARAM`; the message is dropped since [`Status`](crate::Status)
+/// only carries the error code
+impl From<Error> for crate::Status {
+    #[inline]
+    fn from(_e: Error) -> Self {
+        crate::Status::Err(crate::ffi::PLCTAG_ERR_BAD_PARAM)
+    }
+}
+
 impl From<fmt::Error> for Error {
     fn from(_e: fmt::Error) -> Self {
         Error("fmt error")
     }
 }
 
-/// builder to build tag full path
+/// builder to build tag full path,
+/// see [`RawTag::from_builder`](crate::RawTag::from_builder) or [`TagBuilder`] to create the tag
+/// from it directly.
 ///
 /// # Examples
 /// ```rust,no_run
@@ -69,19 +86,39 @@ pub struct PathBuilder {
     auto_sync_write_ms: Option<usize>,
     plc: Option<PlcKind>,
     name: Option<String>,
+    program: Option<String>,
     path: Option<String>,
     gateway: Option<String>,
     use_connected_msg: Option<bool>,
     allow_packing: Option<bool>,
     connection_group_id: Option<u16>,
+    omron_string_defaults: bool,
+    str_is_counted: Option<bool>,
+    str_is_fixed_length: Option<bool>,
+    str_is_zero_terminated: Option<bool>,
+    str_is_byte_swapped: Option<bool>,
+    str_count_word_bytes: Option<u32>,
+    str_max_capacity: Option<u32>,
+    str_total_length: Option<u32>,
+    str_pad_bytes: Option<u32>,
 }
 
 impl PathBuilder {
     /// generic attribute.
     /// defining the current debugging level.
-    /// please use [`plc::set_debug_level`](../plc/fn.set_debug_level.html) instead.
-    #[deprecated]
+    /// please use [`PathBuilder::with_debug`] instead.
+    #[deprecated = "use with_debug() instead"]
     pub fn debug(&mut self, level: DebugLevel) -> &mut Self {
+        self.with_debug(level)
+    }
+
+    /// generic attribute.
+    /// defining the debugging level when the tag is created, writes `&debug=N` into the path.
+    ///
+    /// # Note
+    /// it only affects creation-time logging of the tag.
+    /// use `plctag_log::set_debug_level` to change the debugging level at any time.
+    pub fn with_debug(&mut self, level: DebugLevel) -> &mut Self {
         self.debug = Some(level);
         self
     }
@@ -156,6 +193,14 @@ impl PathBuilder {
         self
     }
 
+    /// EIP only
+    /// program of program-scoped tags, the name will be prefixed with `Program:<program>.`,
+    /// i.e. `program("MyProg").name("MyTag")` emits `name=Program:MyProg.MyTag`
+    pub fn program(&mut self, program: impl AsRef<str>) -> &mut Self {
+        self.program = Some(program.as_ref().to_owned());
+        self
+    }
+
     /// set register for Modbus
     pub fn register(&mut self, reg: Register) -> &mut Self {
         self.name = Some(format!("{}", reg));
@@ -188,19 +233,101 @@ impl PathBuilder {
         self
     }
 
+    /// EIP only
     /// connection group
     pub fn connection_group(&mut self, group_id: u16) -> &mut Self {
         self.connection_group_id = Some(group_id);
         self
     }
 
-    /// check required attributes or conflict attributes
-    fn check(&self) -> Result<()> {
+    /// EIP only
+    /// Optional. Tags with the same `connection_group_id` share a connection to the PLC,
+    /// tags in different groups use separate connections. Same as [`PathBuilder::connection_group`]
+    pub fn connection_group_id(&mut self, group_id: u16) -> &mut Self {
+        self.connection_group(group_id)
+    }
+
+    /// EIP, Omron only
+    /// use string attributes of Omron NJ/NX PLCs:
+    /// `str_is_zero_terminated=0&str_count_word_bytes=2&str_is_byte_swapped=0`.
+    /// Ignored if plc kind is not [`PlcKind::Omron`]
+    pub fn omron_string_defaults(&mut self) -> &mut Self {
+        self.omron_string_defaults = true;
+        self
+    }
+
+    /// string attribute.
+    /// Optional. `str_is_counted`: string has a count word
+    pub fn str_is_counted(&mut self, yes: bool) -> &mut Self {
+        self.str_is_counted = Some(yes);
+        self
+    }
+
+    /// string attribute.
+    /// Optional. `str_is_fixed_length`: string occupies a fixed length
+    pub fn str_is_fixed_length(&mut self, yes: bool) -> &mut Self {
+        self.str_is_fixed_length = Some(yes);
+        self
+    }
+
+    /// string attribute.
+    /// Optional. `str_is_zero_terminated`: string terminates with 0 byte
+    pub fn str_is_zero_terminated(&mut self, yes: bool) -> &mut Self {
+        self.str_is_zero_terminated = Some(yes);
+        self
+    }
+
+    /// string attribute.
+    /// Optional. `str_is_byte_swapped`: bytes of characters are swapped in pairs
+    pub fn str_is_byte_swapped(&mut self, yes: bool) -> &mut Self {
+        self.str_is_byte_swapped = Some(yes);
+        self
+    }
+
+    /// string attribute.
+    /// Optional. `str_count_word_bytes`: size in bytes of the count word
+    pub fn str_count_word_bytes(&mut self, size: u32) -> &mut Self {
+        self.str_count_word_bytes = Some(size);
+        self
+    }
+
+    /// string attribute.
+    /// Optional. `str_max_capacity`: max number of characters
+    pub fn str_max_capacity(&mut self, capacity: u32) -> &mut Self {
+        self.str_max_capacity = Some(capacity);
+        self
+    }
+
+    /// string attribute.
+    /// Optional. `str_total_length`: total bytes occupied by the string
+    pub fn str_total_length(&mut self, size: u32) -> &mut Self {
+        self.str_total_length = Some(size);
+        self
+    }
+
+    /// string attribute.
+    /// Optional. `str_pad_bytes`: padding bytes after the string
+    pub fn str_pad_bytes(&mut self, size: u32) -> &mut Self {
+        self.str_pad_bytes = Some(size);
+        self
+    }
+
+    /// check required attributes or conflict attributes without building the path,
+    /// [`PathBuilder::build`] also validates
+    pub fn validate(&self) -> Result<()> {
         //check protocol, required
         let protocol = match self.protocol {
             None => return Err(Error("protocol required")),
             Some(v) => v,
         };
+        if self.program.is_some() {
+            if protocol != Protocol::EIP {
+                return Err(Error("program is only valid for EIP"));
+            }
+            if self.name.is_none() {
+                return Err(Error("name required for program tags"));
+            }
+        }
         // check required attributes
         match protocol {
             Protocol::EIP => {
@@ -227,9 +354,14 @@ impl PathBuilder {
                 if self.gateway.is_none() {
                     return Err(Error("gateway required"));
                 }
-                if self.name.is_none() {
-                    return Err(Error("name required"));
-                }
+                // register type prefix decides the request limit,
+                // coils and discrete inputs count elements, registers count bytes
+                let (limit, is_bits) = match self.name.as_deref().map(|name| name.get(..2)) {
+                    None => return Err(Error("name required")),
+                    Some(Some("co")) | Some(Some("di")) => (MODBUS_MAX_BITS, true),
+                    Some(Some("hr")) | Some(Some("ir")) => (MODBUS_MAX_BYTES, false),
+                    _ => return Err(Error("modbus name must start with one of co, di, hr, ir")),
+                };
                 //path is number [0-255]
                 match self.path {
                     Some(ref path) => {
@@ -239,8 +371,18 @@ impl PathBuilder {
                     }
                     None => return Err(Error("path required")),
                 }
-                if self.elem_size.is_none() {
-                    return Err(Error("element size required"));
+                let elem_size = match self.elem_size {
+                    Some(v) => v,
+                    None => return Err(Error("element size required")),
+                };
+                let elem_count = self.elem_count.unwrap_or(1);
+                let requested = if is_bits {
+                    elem_count
+                } else {
+                    elem_count.saturating_mul(elem_size)
+                };
+                if requested > limit {
+                    return Err(Error("modbus request too large"));
                 }
             }
         }
@@ -249,7 +391,7 @@ impl PathBuilder {
 
     /// build full tag path
     pub fn build(&self) -> Result<String> {
-        self.check()?;
+        self.validate()?;
         let mut path_buf = String::new();
         let protocol = self.protocol.unwrap();
         write!(path_buf, "protocol={}", protocol)?;
@@ -267,6 +409,19 @@ impl PathBuilder {
                 if let Some(v) = self.allow_packing {
                     write!(path_buf, "&allow_packing={}", v as u8)?;
                 }
+
+                // explicit string attributes take precedence
+                if self.omron_string_defaults && self.plc == Some(PlcKind::Omron) {
+                    if self.str_is_zero_terminated.is_none() {
+                        write!(path_buf, "&str_is_zero_terminated=0")?;
+                    }
+                    if self.str_count_word_bytes.is_none() {
+                        write!(path_buf, "&str_count_word_bytes=2")?;
+                    }
+                    if self.str_is_byte_swapped.is_none() {
+                        write!(path_buf, "&str_is_byte_swapped=0")?;
+                    }
+                }
             }
             Protocol::ModBus => {}
         }
@@ -278,7 +433,10 @@ impl PathBuilder {
             write!(path_buf, "&path={}", path)?;
         }
         if let Some(ref name) = self.name {
-            write!(path_buf, "&name={}", name)?;
+            match self.program {
+                Some(ref program) => write!(path_buf, "&name=Program:{}.{}", program, name)?,
+                None => write!(path_buf, "&name={}", name)?,
+            }
         }
         if let Some(elem_count) = self.elem_count {
             write!(path_buf, "&elem_count={}", elem_count)?
@@ -300,10 +458,33 @@ impl PathBuilder {
             write!(path_buf, "&auto_sync_write_ms={}", ms)?;
         }
 
-        if let Some(group_id) = self.connection_group_id {
+        if let (Protocol::EIP, Some(group_id)) = (protocol, self.connection_group_id) {
             write!(path_buf, "&connection_group_id={}", group_id)?;
         }
 
+        let str_flags = [
+            ("str_is_counted", self.str_is_counted),
+            ("str_is_fixed_length", self.str_is_fixed_length),
+            ("str_is_zero_terminated", self.str_is_zero_terminated),
+            ("str_is_byte_swapped", self.str_is_byte_swapped),
+        ];
+        for (name, yes) in str_flags {
+            if let Some(yes) = yes {
+                write!(path_buf, "&{}={}", name, yes as u8)?;
+            }
+        }
+        let str_sizes = [
+            ("str_count_word_bytes", self.str_count_word_bytes),
+            ("str_max_capacity", self.str_max_capacity),
+            ("str_total_length", self.str_total_length),
+            ("str_pad_bytes", self.str_pad_bytes),
+        ];
+        for (name, size) in str_sizes {
+            if let Some(size) = size {
+                write!(path_buf, "&{}={}", name, size)?;
+            }
+        }
+
         if let Some(debug) = self.debug {
             let level = debug as u8;
             write!(path_buf, "&debug={}", level)?;
@@ -312,6 +493,94 @@ impl PathBuilder {
     }
 }
 
+/// builder to create a ready-to-use [`RawTag`](crate::RawTag) in one call, carrying the creation
+/// timeout and `auto_sync_*` settings.
+/// string attributes are part of the tag path, see [`PathBuilder`].
+///
+/// # Examples
+/// ```rust,no_run
+/// use plctag_core::builder::*;
+///
+/// let mut path = PathBuilder::default();
+/// path.protocol(Protocol::EIP)
+///     .gateway("192.168.1.120")
+///     .plc(PlcKind::ControlLogix)
+///     .name("MyTag1")
+///     .path("1,0")
+///     .str_max_capacity(82);
+/// let tag = TagBuilder::from_path_builder(&path)
+///     .unwrap()
+///     .timeout(100)
+///     .auto_sync_read_ms(200)
+///     .connect()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TagBuilder {
+    path: String,
+    timeout: u32,
+    auto_sync_read_ms: Option<u32>,
+    auto_sync_write_ms: Option<u32>,
+}
+
+impl TagBuilder {
+    /// create builder from tag path
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            timeout: 0,
+            auto_sync_read_ms: None,
+            auto_sync_write_ms: None,
+        }
+    }
+
+    /// create builder from [`PathBuilder`]
+    pub fn from_path_builder(builder: &PathBuilder) -> Result<Self> {
+        Ok(Self::new(builder.build()?))
+    }
+
+    /// timeout in milliseconds to wait for the tag creation.
+    /// defaults to 0, not waiting; the tag status is pending until created,
+    /// see [`RawTag::wait`](crate::RawTag::wait)
+    pub fn timeout(&mut self, millis: u32) -> &mut Self {
+        self.timeout = millis;
+        self
+    }
+
+    /// An integer number of milliseconds to periodically read data from the PLC,
+    /// overrides `auto_sync_read_ms` of the tag path
+    pub fn auto_sync_read_ms(&mut self, millis: u32) -> &mut Self {
+        self.auto_sync_read_ms = Some(millis);
+        self
+    }
+
+    /// An integer number of milliseconds to buffer tag data changes before writing to the PLC,
+    /// overrides `auto_sync_write_ms` of the tag path
+    pub fn auto_sync_write_ms(&mut self, millis: u32) -> &mut Self {
+        self.auto_sync_write_ms = Some(millis);
+        self
+    }
+
+    /// create the tag, waiting for creation if timeout > 0, then apply `auto_sync_*` settings
+    pub fn connect(&self) -> crate::Result<crate::RawTag> {
+        use crate::IntAttr;
+
+        let tag = crate::RawTag::new(self.path.as_str(), self.timeout)?;
+        let settings = [
+            (IntAttr::AutoSyncReadMs, self.auto_sync_read_ms),
+            (IntAttr::AutoSyncWriteMs, self.auto_sync_write_ms),
+        ];
+        for (attr, millis) in settings {
+            if let Some(millis) = millis {
+                let millis = i32::try_from(millis)
+                    .map_err(|_| crate::Status::Err(crate::ffi::PLCTAG_ERR_BAD_PARAM))?;
+                tag.set_attr(attr.name(), millis)?;
+            }
+        }
+        Ok(tag)
+    }
+}
+
 /// library supported protocols
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Protocol {
@@ -330,6 +599,28 @@ impl fmt::Display for Protocol {
     }
 }
 
+impl FromStr for Protocol {
+    type Err = Error;
+
+    /// parse protocol, case insensitive, i.e. `ab-eip`, `ab_eip`, `modbus-tcp`
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "ab-eip" | "ab_eip" | "eip" => Ok(Protocol::EIP),
+            "modbus-tcp" | "modbus_tcp" | "modbus" => Ok(Protocol::ModBus),
+            _ => Err(Error("unknown protocol")),
+        }
+    }
+}
+
+impl TryFrom<&str> for Protocol {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(s: &str) -> Result<Self> {
+        s.parse()
+    }
+}
+
 /// modbus supported register
 pub enum Register {
     /// coil registers
@@ -386,6 +677,38 @@ impl fmt::Display for PlcKind {
     }
 }
 
+impl FromStr for PlcKind {
+    type Err = Error;
+
+    /// parse plc kind, case insensitive, accepts the same names and aliases as `libplctag`
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "controllogix" | "contrologix" | "compactlogix" | "clgx" | "lgx" | "logix" => {
+                Ok(PlcKind::ControlLogix)
+            }
+            "plc5" | "plc-5" | "plc" => Ok(PlcKind::PLC5),
+            "slc500" | "slc-500" | "slc" => Ok(PlcKind::SLC500),
+            "logixpccc" | "logix-pccc" | "lgxpccc" | "lgx-pccc" | "logixplc5" | "logix-plc5"
+            | "lgxplc5" | "lgx-plc5" => Ok(PlcKind::LogixPCCC),
+            "micro800" | "micro-800" => Ok(PlcKind::Micro800),
+            "micrologix" | "micro-logix" | "mlgx" => Ok(PlcKind::MicroLogix),
+            "omron" | "omron-njnx" | "omron-nj" | "omron-nx" | "njnx" | "nx1p2" => {
+                Ok(PlcKind::Omron)
+            }
+            _ => Err(Error("unknown plc kind")),
+        }
+    }
+}
+
+impl TryFrom<&str> for PlcKind {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(s: &str) -> Result<Self> {
+        s.parse()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,4 +743,244 @@ mod tests {
             .unwrap();
         assert_eq!(path, "protocol=modbus-tcp&gateway=192.168.1.120:502&path=0&name=co42&elem_count=1&elem_size=16&read_cache_ms=0");
     }
+
+    #[test]
+    fn test_debug_builder() {
+        let path = PathBuilder::default()
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .plc(PlcKind::ControlLogix)
+            .name("MyTag1")
+            .path("1,0")
+            .with_debug(DebugLevel::Detail)
+            .build()
+            .unwrap();
+        assert!(path.ends_with("&debug=4"));
+    }
+
+    #[test]
+    fn test_modbus_builder_check() {
+        let res = PathBuilder::default()
+            .protocol(Protocol::ModBus)
+            .gateway("192.168.1.120:502")
+            .path("0")
+            .register(Register::Coil(42))
+            .element_size(16)
+            .element_count(2001)
+            .build();
+        assert_eq!(res.unwrap_err().to_string(), "modbus request too large");
+
+        let res = PathBuilder::default()
+            .protocol(Protocol::ModBus)
+            .gateway("192.168.1.120:502")
+            .path("0")
+            .register(Register::Coil(42))
+            .element_size(16)
+            .element_count(200)
+            .build();
+        assert!(res.is_ok());
+
+        let res = PathBuilder::default()
+            .protocol(Protocol::ModBus)
+            .gateway("192.168.1.120:502")
+            .path("0")
+            .name("xx42")
+            .element_size(16)
+            .build();
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_omron_string_defaults() {
+        let path = PathBuilder::default()
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .plc(PlcKind::Omron)
+            .name("MyTag1")
+            .element_size(88)
+            .omron_string_defaults()
+            .build()
+            .unwrap();
+        assert_eq!(path, "protocol=ab-eip&plc=omron-njnx&str_is_zero_terminated=0&str_count_word_bytes=2&str_is_byte_swapped=0&gateway=192.168.1.120&name=MyTag1&elem_size=88");
+
+        let path = PathBuilder::default()
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .plc(PlcKind::ControlLogix)
+            .name("MyTag1")
+            .path("1,0")
+            .omron_string_defaults()
+            .build()
+            .unwrap();
+        assert!(!path.contains("str_"));
+    }
+
+    #[test]
+    fn test_parse_protocol() {
+        assert_eq!("ab-eip".parse::<Protocol>().unwrap(), Protocol::EIP);
+        assert_eq!("AB_EIP".parse::<Protocol>().unwrap(), Protocol::EIP);
+        assert_eq!(Protocol::try_from("modbus-tcp").unwrap(), Protocol::ModBus);
+        assert!("ab-cip".parse::<Protocol>().is_err());
+    }
+
+    #[test]
+    fn test_parse_plc_kind() {
+        let kinds = [
+            PlcKind::ControlLogix,
+            PlcKind::PLC5,
+            PlcKind::SLC500,
+            PlcKind::LogixPCCC,
+            PlcKind::Micro800,
+            PlcKind::MicroLogix,
+            PlcKind::Omron,
+        ];
+        // round trip with Display
+        for kind in kinds {
+            assert_eq!(kind.to_string().parse::<PlcKind>().unwrap(), kind);
+        }
+        assert_eq!(PlcKind::try_from("omron-nj").unwrap(), PlcKind::Omron);
+        assert_eq!(PlcKind::try_from("omron-nx").unwrap(), PlcKind::Omron);
+        assert_eq!(PlcKind::try_from("njnx").unwrap(), PlcKind::Omron);
+        assert_eq!(
+            PlcKind::try_from("CompactLogix").unwrap(),
+            PlcKind::ControlLogix
+        );
+        assert!(PlcKind::try_from("s7-1200").is_err());
+    }
+
+    #[test]
+    fn test_connection_group_id() {
+        let path = PathBuilder::default()
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .plc(PlcKind::ControlLogix)
+            .name("MyTag1")
+            .path("1,0")
+            .connection_group_id(2)
+            .build()
+            .unwrap();
+        assert!(path.ends_with("&connection_group_id=2"));
+
+        let path = PathBuilder::default()
+            .protocol(Protocol::ModBus)
+            .gateway("192.168.1.120:502")
+            .path("0")
+            .register(Register::Holding(1))
+            .element_size(2)
+            .connection_group_id(2)
+            .build()
+            .unwrap();
+        assert!(!path.contains("connection_group_id"));
+    }
+
+    #[test]
+    fn test_tag_builder() {
+        let path = "make=system&family=library&name=debug&debug=4&str_max_capacity=20";
+        let tag = TagBuilder::new(path)
+            .timeout(100)
+            .auto_sync_read_ms(200)
+            .connect()
+            .unwrap();
+        assert!(tag.status().is_ok());
+        assert_eq!(tag.string_attrs().max_capacity().unwrap(), 20);
+        assert_eq!(tag.attr(crate::IntAttr::AutoSyncReadMs).unwrap(), 200);
+    }
+
+    #[test]
+    fn test_string_attrs_builder() {
+        let path = PathBuilder::default()
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .plc(PlcKind::Omron)
+            .name("MyTag1")
+            .element_size(88)
+            .omron_string_defaults()
+            .str_count_word_bytes(4)
+            .str_max_capacity(82)
+            .str_is_counted(true)
+            .build()
+            .unwrap();
+        assert_eq!(path, "protocol=ab-eip&plc=omron-njnx&str_is_zero_terminated=0&str_is_byte_swapped=0&gateway=192.168.1.120&name=MyTag1&elem_size=88&str_is_counted=1&str_count_word_bytes=4&str_max_capacity=82");
+    }
+
+    #[test]
+    fn test_program_builder() {
+        let path = PathBuilder::default()
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .plc(PlcKind::ControlLogix)
+            .path("1,0")
+            .program("Foo")
+            .name("Bar")
+            .build()
+            .unwrap();
+        assert_eq!(
+            path,
+            "protocol=ab-eip&plc=controllogix&gateway=192.168.1.120&path=1,0&name=Program:Foo.Bar"
+        );
+
+        let res = PathBuilder::default()
+            .protocol(Protocol::ModBus)
+            .gateway("192.168.1.120:502")
+            .path("0")
+            .program("Foo")
+            .register(Register::Holding(1))
+            .element_size(2)
+            .build();
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "program is only valid for EIP"
+        );
+    }
+
+    #[test]
+    fn test_error_into_status() {
+        fn create() -> crate::Result<String> {
+            let path = PathBuilder::default().name("MyTag1").build()?;
+            Ok(path)
+        }
+        let status = create().unwrap_err();
+        assert_eq!(status.code(), crate::ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    #[test]
+    fn test_validate() {
+        let res = PathBuilder::default().name("MyTag1").validate();
+        assert_eq!(res.unwrap_err().to_string(), "protocol required");
+
+        let res = PathBuilder::default()
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .name("MyTag1")
+            .validate();
+        assert_eq!(res.unwrap_err().to_string(), "plc kind required");
+
+        let res = PathBuilder::default()
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .plc(PlcKind::ControlLogix)
+            .name("MyTag1")
+            .validate();
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "path required for controllogix"
+        );
+
+        let res = PathBuilder::default()
+            .protocol(Protocol::ModBus)
+            .path("0")
+            .register(Register::Holding(1))
+            .element_size(2)
+            .validate();
+        assert_eq!(res.unwrap_err().to_string(), "gateway required");
+
+        let res = PathBuilder::default()
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .plc(PlcKind::ControlLogix)
+            .name("MyTag1")
+            .path("1,0")
+            .validate();
+        assert!(res.is_ok());
+    }
 }
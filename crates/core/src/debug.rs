@@ -4,6 +4,7 @@
 // Copyright: 2022, Joylei <leingliu@gmail.com>
 // License: MIT
 
+use crate::{ffi, Status};
 use core::convert::From;
 
 /// provides debugging output when enabled
@@ -24,6 +25,8 @@ pub enum DebugLevel {
     Spew,
 }
 
+/// out of range values are mapped to [`DebugLevel::None`] for backward compatibility,
+/// use [`DebugLevel::try_from_u8`] to reject them
 impl From<u8> for DebugLevel {
     #[inline]
     fn from(val: u8) -> DebugLevel {
@@ -38,3 +41,47 @@ impl From<u8> for DebugLevel {
         }
     }
 }
+
+impl DebugLevel {
+    /// returns `PLCTAG_ERR_OUT_OF_BOUNDS` for values other than 0-5.
+    ///
+    /// `TryFrom<u8>` is not implemented since it comes with [`From<u8>`](DebugLevel::from)
+    #[inline]
+    pub fn try_from_u8(val: u8) -> Result<DebugLevel, Status> {
+        match val {
+            0..=5 => Ok(val.into()),
+            _ => Err(Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS)),
+        }
+    }
+}
+
+impl From<DebugLevel> for u8 {
+    #[inline]
+    fn from(level: DebugLevel) -> u8 {
+        level as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_level() {
+        let levels = [
+            DebugLevel::None,
+            DebugLevel::Error,
+            DebugLevel::Warn,
+            DebugLevel::Info,
+            DebugLevel::Detail,
+            DebugLevel::Spew,
+        ];
+        for (i, level) in levels.into_iter().enumerate() {
+            assert_eq!(u8::from(level), i as u8);
+            assert_eq!(DebugLevel::try_from_u8(i as u8).unwrap(), level);
+        }
+        let res = DebugLevel::try_from_u8(6);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+        assert_eq!(DebugLevel::from(6), DebugLevel::None);
+    }
+}
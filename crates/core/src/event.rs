@@ -0,0 +1,380 @@
+// plctag-rs
+//
+// a rust wrapper of libplctag, with rust style APIs and useful extensions.
+// Copyright: 2022, Joylei <leingliu@gmail.com>
+// License: MIT
+
+//! safe tag event callbacks, see [`RawTag::on_event`]
+
+use crate::*;
+use alloc::collections::BTreeMap;
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Condvar, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+
+type Handler = Box<dyn FnMut(Event, Status) + Send>;
+
+/// registered handler, `token` identifies the registration that owns it
+struct Entry {
+    token: u64,
+    handler: Arc<Mutex<Handler>>,
+}
+
+/// registered handlers, keyed by tag id
+static HANDLERS: Mutex<BTreeMap<i32, Entry>> = Mutex::new(BTreeMap::new());
+
+/// serializes registering and unregistering, which call into `libplctag`;
+/// the trampoline only takes [`HANDLERS`], so it never waits on this lock
+static REGISTRATION: Mutex<()> = Mutex::new(());
+
+/// token of the next registration
+static NEXT_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+/// lock the registry, a panicking handler must not disable events of other tags
+#[inline]
+fn handlers() -> MutexGuard<'static, BTreeMap<i32, Entry>> {
+    HANDLERS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[inline]
+fn registration() -> MutexGuard<'static, ()> {
+    REGISTRATION.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// drop the handler of a destroyed tag, the tag id might be reused by another tag
+#[inline]
+pub(crate) fn remove_handler(tag_id: i32) {
+    handlers().remove(&tag_id);
+}
+
+/// tag events
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Event {
+    /// tag created
+    Created,
+    /// read started
+    ReadStarted,
+    /// read completed
+    ReadCompleted,
+    /// write started
+    WriteStarted,
+    /// write completed
+    WriteCompleted,
+    /// pending operation aborted
+    Aborted,
+    /// tag destroyed
+    Destroyed,
+    /// other event codes
+    Other(i32),
+}
+
+impl From<i32> for Event {
+    #[inline]
+    fn from(val: i32) -> Event {
+        match val as u32 {
+            ffi::PLCTAG_EVENT_CREATED => Event::Created,
+            ffi::PLCTAG_EVENT_READ_STARTED => Event::ReadStarted,
+            ffi::PLCTAG_EVENT_READ_COMPLETED => Event::ReadCompleted,
+            ffi::PLCTAG_EVENT_WRITE_STARTED => Event::WriteStarted,
+            ffi::PLCTAG_EVENT_WRITE_COMPLETED => Event::WriteCompleted,
+            ffi::PLCTAG_EVENT_ABORTED => Event::Aborted,
+            ffi::PLCTAG_EVENT_DESTROYED => Event::Destroyed,
+            _ => Event::Other(val),
+        }
+    }
+}
+
+/// unregisters the event handler when dropped.
+/// it does nothing if the handler has been replaced by a newer one, or the tag has been dropped.
+#[derive(Debug)]
+#[must_use = "the handler is unregistered when the guard drops"]
+pub struct EventGuard {
+    tag_id: i32,
+    token: u64,
+}
+
+impl Drop for EventGuard {
+    #[inline]
+    fn drop(&mut self) {
+        let _lock = registration();
+        let removed = {
+            let mut handlers = handlers();
+            match handlers.get(&self.tag_id) {
+                Some(entry) if entry.token == self.token => handlers.remove(&self.tag_id),
+                _ => None,
+            }
+        };
+        if removed.is_some() {
+            unsafe { ffi::plc_tag_unregister_callback(self.tag_id) };
+        }
+    }
+}
+
+unsafe extern "C" fn on_event_trampoline(tag_id: i32, event: i32, status: i32) {
+    let handler = handlers().get(&tag_id).map(|entry| entry.handler.clone());
+    if let Some(handler) = handler {
+        let mut f = handler.lock().unwrap_or_else(PoisonError::into_inner);
+        // never unwind into the C library
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| f(event.into(), status.into())));
+    }
+}
+
+impl RawTag {
+    /// register a closure to receive tag events.
+    ///
+    /// note: registering a new handler will override existing one, including callbacks
+    /// registered by [`RawTag::register_callback`]. The handler is unregistered when
+    /// the returned guard drops, unless it has been overridden; please keep the guard alive
+    /// before tag drops.
+    pub fn on_event(&self, f: Box<dyn FnMut(Event, Status) + Send>) -> Result<EventGuard> {
+        self.add_handler(f, true)
+    }
+
+    /// register the handler, fails with `PLCTAG_ERR_DUPLICATE` if a handler or a raw callback
+    /// exists and not `replace`
+    fn add_handler(&self, f: Handler, replace: bool) -> Result<EventGuard> {
+        let tag_id = self.id();
+        let token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+        let _lock = registration();
+        {
+            let mut handlers = handlers();
+            if !replace && (handlers.contains_key(&tag_id) || self.has_raw_callback()) {
+                return Err(Status::Err(ffi::PLCTAG_ERR_DUPLICATE));
+            }
+            let handler = Arc::new(Mutex::new(f));
+            handlers.insert(tag_id, Entry { token, handler });
+        }
+        let status = unsafe { self.replace_callback(Some(on_event_trampoline)) };
+        if let Err(e) = status.into_result() {
+            handlers().remove(&tag_id);
+            return Err(e);
+        }
+        // the raw callback if any is overridden
+        self.clear_raw_callback();
+        Ok(EventGuard { tag_id, token })
+    }
+
+    /// block until the event fires, returns the status of the event.
+    /// it's a timeout error if the event does not fire in time; wait forever if `timeout` is `None`.
+    ///
+    /// note: it registers a handler like [`RawTag::on_event`] during the wait, and fails with
+    /// `PLCTAG_ERR_DUPLICATE` if a handler registered by [`RawTag::on_event`] or a callback
+    /// registered by [`RawTag::register_callback`], [`RawTag::register_callback_ex`] or
+    /// [`RawTag::new_with_callback`] is alive, instead of overriding it.
+    /// The listener is registered when called, perform the operation from another thread,
+    /// i.e. wait for [`Event::ReadCompleted`] while another thread reads;
+    /// see [`RawTag::wait_for_with`] to perform the operation in the same thread.
+    #[inline]
+    pub fn wait_for(&self, event: Event, timeout: Option<Duration>) -> Result<()> {
+        self.wait_for_with(event, timeout, |_| Status::Ok)
+    }
+
+    /// register the listener, perform the operation `op`, then block until the event fires,
+    /// so the event can not be missed, see [`RawTag::wait_for`].
+    /// returns the error of `op` without waiting; pending is not an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use plctag_core::{Event, RawTag};
+    /// use std::time::Duration;
+    ///
+    /// let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    /// tag.wait_for_with(Event::ReadCompleted, Some(Duration::from_secs(1)), |tag| tag.read(0))
+    ///     .unwrap();
+    /// ```
+    pub fn wait_for_with(
+        &self,
+        event: Event,
+        timeout: Option<Duration>,
+        op: impl FnOnce(&Self) -> Status,
+    ) -> Result<()> {
+        let pair = Arc::new((Mutex::new(None), Condvar::new()));
+        let _guard = {
+            let pair = pair.clone();
+            self.add_handler(
+                Box::new(move |ev, status| {
+                    if ev == event {
+                        let (lock, cvar) = &*pair;
+                        *lock.lock().unwrap_or_else(PoisonError::into_inner) = Some(status);
+                        cvar.notify_all();
+                    }
+                }),
+                false,
+            )?
+        };
+
+        let status = op(self);
+        if status.is_err() {
+            return Err(status);
+        }
+
+        let deadline = timeout.map(|v| Instant::now() + v);
+        let (lock, cvar) = &*pair;
+        let mut fired = lock.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(status) = *fired {
+                return status.into_result();
+            }
+            fired = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(Status::Err(ffi::PLCTAG_ERR_TIMEOUT));
+                    }
+                    cvar.wait_timeout(fired, deadline - now)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => cvar.wait(fired).unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::mpsc, thread};
+
+    #[test]
+    fn test_on_event() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let _guard = tag
+            .on_event(Box::new(move |event, status| {
+                let _ = tx.send((event, status));
+            }))
+            .unwrap();
+
+        let status = tag.read(100);
+        assert!(status.is_ok());
+
+        let mut completed = false;
+        while let Ok((event, status)) = rx.recv_timeout(Duration::from_millis(500)) {
+            if event == Event::ReadCompleted {
+                assert!(status.is_ok());
+                completed = true;
+                break;
+            }
+        }
+        assert!(completed);
+    }
+
+    #[test]
+    fn test_wait_for() {
+        let tag =
+            Arc::new(RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap());
+        let reader = {
+            let tag = tag.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                tag.read(100)
+            })
+        };
+        let res = tag.wait_for(Event::ReadCompleted, Some(Duration::from_secs(1)));
+        assert!(res.is_ok());
+        assert!(reader.join().unwrap().is_ok());
+
+        let res = tag.wait_for(Event::WriteCompleted, Some(Duration::from_millis(50)));
+        assert!(res.unwrap_err().is_timeout());
+    }
+
+    #[test]
+    fn test_wait_for_with() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let res = tag.wait_for_with(Event::ReadCompleted, Some(Duration::from_secs(1)), |tag| {
+            tag.read(0)
+        });
+        assert!(res.is_ok());
+
+        // error of the operation is returned
+        let res = tag.wait_for_with(Event::ReadCompleted, None, |_| {
+            Status::Err(ffi::PLCTAG_ERR_BAD_PARAM)
+        });
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    unsafe extern "C" fn noop_callback(_tag_id: i32, _event: i32, _status: i32) {}
+
+    #[test]
+    fn test_wait_for_raw_callback() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let status = unsafe { tag.register_callback(Some(noop_callback)) };
+        assert!(status.is_ok());
+        // the raw callback must not be overridden
+        let res = tag.wait_for(Event::ReadCompleted, Some(Duration::from_millis(50)));
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_DUPLICATE);
+
+        assert!(tag.unregister_callback().is_ok());
+        let res = tag.wait_for(Event::ReadCompleted, Some(Duration::from_millis(50)));
+        assert!(res.unwrap_err().is_timeout());
+    }
+
+    #[test]
+    fn test_on_event_panic() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let guard = tag
+            .on_event(Box::new(|_, _| panic!("handler panics")))
+            .unwrap();
+        assert!(tag.read(100).is_ok());
+        drop(guard);
+
+        // events still delivered after a handler panicked
+        let (tx, rx) = mpsc::channel();
+        let _guard = tag
+            .on_event(Box::new(move |event, _| {
+                let _ = tx.send(event);
+            }))
+            .unwrap();
+        assert!(tag.read(100).is_ok());
+        let mut completed = false;
+        while let Ok(event) = rx.recv_timeout(Duration::from_millis(500)) {
+            if event == Event::ReadCompleted {
+                completed = true;
+                break;
+            }
+        }
+        assert!(completed);
+    }
+
+    #[test]
+    fn test_on_event_replaced() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let first = tag.on_event(Box::new(|_, _| {})).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let _second = tag
+            .on_event(Box::new(move |event, _| {
+                let _ = tx.send(event);
+            }))
+            .unwrap();
+        // the first guard must not unregister the second handler
+        drop(first);
+
+        assert!(tag.read(100).is_ok());
+        let mut completed = false;
+        while let Ok(event) = rx.recv_timeout(Duration::from_millis(500)) {
+            if event == Event::ReadCompleted {
+                completed = true;
+                break;
+            }
+        }
+        assert!(completed);
+    }
+
+    #[test]
+    fn test_wait_for_existing_handler() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let guard = tag.on_event(Box::new(|_, _| {})).unwrap();
+        let res = tag.wait_for(Event::ReadCompleted, Some(Duration::from_millis(50)));
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_DUPLICATE);
+
+        drop(guard);
+        let res = tag.wait_for(Event::ReadCompleted, Some(Duration::from_millis(50)));
+        assert!(res.unwrap_err().is_timeout());
+    }
+}
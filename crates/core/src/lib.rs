@@ -20,7 +20,7 @@ Add `plctag-core` to your Cargo.toml
 
 ```toml
 [dependencies]
-plctag-core= "0.4"
+plctag-core= "0.5"
 ```
 
 ## Examples
@@ -72,9 +72,13 @@ pub mod ffi {
     pub use plctag_sys::*;
 }
 
+pub mod bcd;
 #[cfg(feature = "builder")]
 pub mod builder;
 mod debug;
+#[cfg(feature = "callback")]
+pub mod event;
+pub mod modbus;
 mod raw;
 mod status;
 #[cfg(feature = "value")]
@@ -82,8 +86,16 @@ mod value;
 
 /// plctag result
 pub type Result<T> = std::result::Result<T, Status>;
-pub use raw::RawTag;
-pub use status::Status;
+pub use raw::{
+    get_global_attr, library_version, set_global_attr, shutdown, DiffWriter, IntAttr, RawTag,
+    ReadOp, StringAttrs, StringLayout, Transaction,
+};
+pub use status::{ConnectionStatus, Status, StatusKind};
 
+#[cfg(feature = "callback")]
+pub use event::{Event, EventGuard};
+
+#[cfg(feature = "value")]
+pub use raw::ValueIter;
 #[cfg(feature = "value")]
-pub use value::{Decode, Encode, ValueExt};
+pub use value::{Be, Bytes, Decode, Encode, Le, ValueExt};
@@ -0,0 +1,109 @@
+// plctag-rs
+//
+// a rust wrapper of libplctag, with rust style APIs and useful extensions.
+// Copyright: 2022, Joylei <leingliu@gmail.com>
+// License: MIT
+
+//! helpers for Modbus tags
+//!
+//! holding/input registers are 16-bit words, addressed by register index; coils/discrete inputs
+//! are bits, addressed by bit index. All helpers work on the tag buffer only, please read the
+//! tag before getting values, or write the tag after setting values.
+//!
+//! # Examples
+//! ```rust,no_run
+//! use plctag_core::{modbus, RawTag};
+//!
+//! let path = "protocol=modbus-tcp&gateway=192.168.1.120&path=0&name=hr0&elem_count=10";
+//! let tag = RawTag::new(path, 100).unwrap();
+//! tag.read(100).into_result().unwrap();
+//! let registers = modbus::read_holding(&tag, 0, 10).unwrap();
+//! ```
+
+use crate::{ffi, raw::element_offset, RawTag, Result, Status};
+
+/// byte offset of register `start`, 2 bytes per register;
+/// `PLCTAG_ERR_OUT_OF_BOUNDS` if the end of `count` registers overflows
+#[inline(always)]
+fn register_offset(start: u32, count: usize) -> Result<u32> {
+    let base = start
+        .checked_mul(2)
+        .ok_or(Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS))?;
+    element_offset(base, count, 2)?;
+    Ok(base)
+}
+
+/// get `count` holding registers from register index `start`
+#[inline]
+pub fn read_holding(tag: &RawTag, start: u32, count: usize) -> Result<Vec<u16>> {
+    let base = register_offset(start, count)?;
+    (0..count as u32)
+        .map(|i| tag.get_u16(base + i * 2))
+        .collect()
+}
+
+/// set holding registers from register index `start`
+#[inline]
+pub fn write_holding(tag: &RawTag, start: u32, values: &[u16]) -> Result<()> {
+    let base = register_offset(start, values.len())?;
+    for (i, value) in values.iter().enumerate() {
+        tag.set_u16(base + i as u32 * 2, *value)?;
+    }
+    Ok(())
+}
+
+/// get `count` coils from coil index `start`
+#[inline]
+pub fn read_coils(tag: &RawTag, start: u32, count: usize) -> Result<Vec<bool>> {
+    tag.get_bool_array(start, count)
+}
+
+/// set coils from coil index `start`
+#[inline]
+pub fn write_coils(tag: &RawTag, start: u32, values: &[bool]) -> Result<()> {
+    tag.set_bool_array(start, values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_tag() -> RawTag {
+        RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap()
+    }
+
+    #[test]
+    fn test_holding_registers() {
+        let tag = mock_tag();
+        write_holding(&tag, 1, &[0x1234, 0xABCD]).unwrap();
+        assert_eq!(tag.get_u16(2).unwrap(), 0x1234);
+        assert_eq!(tag.get_u16(4).unwrap(), 0xABCD);
+
+        let registers = read_holding(&tag, 1, 2).unwrap();
+        assert_eq!(registers, vec![0x1234, 0xABCD]);
+    }
+
+    #[test]
+    fn test_coils() {
+        let tag = mock_tag();
+        let coils = [true, false, true, true];
+        write_coils(&tag, 3, &coils).unwrap();
+        assert!(tag.get_bit(3).unwrap());
+        assert!(!tag.get_bit(4).unwrap());
+
+        let values = read_coils(&tag, 3, 4).unwrap();
+        assert_eq!(values, coils);
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let tag = mock_tag();
+        let size = tag.size().unwrap();
+        assert!(read_holding(&tag, size / 2, 1).is_err());
+
+        let res = read_holding(&tag, u32::MAX, 1);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+        let res = write_holding(&tag, u32::MAX / 2, &[1, 2]);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+    }
+}
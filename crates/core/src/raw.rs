@@ -6,20 +6,41 @@
 
 use crate::*;
 use std::{
+    cell::RefCell,
     ffi::{c_void, CString},
+    sync::atomic::{AtomicU32, Ordering},
     thread,
     time::{Duration, Instant},
 };
 
 /// wrapper of tag model based on `libplctag`
+///
+/// # Thread Safety
+/// [`RawTag`] is `Send` and `Sync`: it only holds the tag id and path, and every `libplctag` API
+/// call on a tag id is guarded by the library's internal tag mutex.
+/// however, the tag data buffer is shared, concurrent `set_*`/`write` or `read`/`get_*` sequences
+/// from different threads interleave; wrap the tag in a `Mutex` to make such sequences atomic.
 #[derive(Debug)]
 pub struct RawTag {
     tag_id: i32,
+    /// path used to create the tag, see [`RawTag::try_clone`]
+    path: CString,
+    /// cached tag size, `NO_SIZE` if not cached; atomic to keep [`RawTag`] `Sync`
+    size_cache: AtomicU32,
+    /// a callback registered by [`RawTag::register_callback`], [`RawTag::register_callback_ex`]
+    /// or [`RawTag::new_with_callback`] is alive
+    #[cfg(feature = "callback")]
+    raw_callback: std::sync::atomic::AtomicBool,
 }
 
+/// tag size not cached
+const NO_SIZE: u32 = u32::MAX;
+
 impl RawTag {
     /// create new [`RawTag`]
     ///
+    /// returns `PLCTAG_ERR_BAD_PARAM` if path contains 0 byte.
+    ///
     /// # Note
     /// if you passed wrong path parameters, your program might crash.
     /// you might want to use `PathBuilder` to build a path.
@@ -36,12 +57,35 @@ impl RawTag {
     /// See https://github.com/libplctag/libplctag/wiki/Tag-String-Attributes for tag string attributes.
     ///
     pub fn new<P: Into<Vec<u8>>>(path: P, timeout: u32) -> Result<Self> {
-        let path = CString::new(path).unwrap();
+        let path = to_cstring(path)?;
         let tag_id = unsafe { ffi::plc_tag_create(path.as_ptr(), timeout as i32) };
         if tag_id < 0 {
             return Err(Status::new(tag_id));
         }
-        Ok(Self { tag_id })
+        Ok(Self::from_parts(tag_id, path))
+    }
+
+    /// create new [`RawTag`] from [`PathBuilder`](crate::builder::PathBuilder).
+    /// builder errors are returned as `PLCTAG_ERR_BAD_PARAM`
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use plctag_core::builder::*;
+    /// use plctag_core::RawTag;
+    ///
+    /// let mut builder = PathBuilder::default();
+    /// builder
+    ///     .protocol(Protocol::EIP)
+    ///     .gateway("192.168.1.120")
+    ///     .plc(PlcKind::ControlLogix)
+    ///     .name("MyTag1")
+    ///     .path("1,0");
+    /// let tag = RawTag::from_builder(&builder, 100).unwrap();
+    /// ```
+    #[cfg(feature = "builder")]
+    pub fn from_builder(builder: &crate::builder::PathBuilder, timeout: u32) -> Result<Self> {
+        let path = builder.build()?;
+        Self::new(path, timeout)
     }
 
     /// create new [`RawTag`]
@@ -60,27 +104,69 @@ impl RawTag {
         >,
         user_data: *mut c_void,
     ) -> Result<Self> {
-        let path = CString::new(path).unwrap();
+        let path = to_cstring(path)?;
         let tag_id = ffi::plc_tag_create_ex(path.as_ptr(), cb, user_data, timeout as i32);
         if tag_id < 0 {
             return Err(Status::new(tag_id));
         }
-        Ok(Self { tag_id })
+        let tag = Self::from_parts(tag_id, path);
+        tag.raw_callback_replaced(cb.is_some());
+        Ok(tag)
+    }
+
+    #[inline(always)]
+    fn from_parts(tag_id: i32, path: CString) -> Self {
+        Self {
+            tag_id,
+            path,
+            size_cache: AtomicU32::new(NO_SIZE),
+            #[cfg(feature = "callback")]
+            raw_callback: std::sync::atomic::AtomicBool::new(false),
+        }
     }
 
-    /// tag id
+    /// the underlying `libplctag` tag id, for calling `libplctag` functions not wrapped yet.
+    ///
+    /// # Note
+    /// do not destroy it; the id is valid as long as this [`RawTag`] is alive.
     #[inline(always)]
     pub fn id(&self) -> i32 {
         self.tag_id
     }
 
+    /// create an independent [`RawTag`] with the same path.
+    ///
+    /// # Note
+    /// the new tag has its own tag data buffer; callbacks are not copied.
+    #[inline]
+    pub fn try_clone(&self, timeout: u32) -> Result<Self> {
+        Self::new(self.path.as_bytes(), timeout)
+    }
+
+    /// connection state guessed from tag status, see [`ConnectionStatus`] for the heuristic
+    #[inline]
+    pub fn connection_status(&self) -> ConnectionStatus {
+        self.status().into()
+    }
+
+    /// is connected? see [`ConnectionStatus`] for the heuristic
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        matches!(self.connection_status(), ConnectionStatus::Connected)
+    }
+
     /// perform read operation.
     /// - blocking read if timeout > 0
     /// - non-blocking read if timeout = 0
     #[inline(always)]
     pub fn read(&self, timeout: u32) -> Status {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("plctag.read", tag_id = self.tag_id, timeout).entered();
         let rc = unsafe { ffi::plc_tag_read(self.tag_id, timeout as i32) };
-        rc.into()
+        let status: Status = rc.into();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(status = %status, "read done");
+        status
     }
 
     /// perform write operation
@@ -88,8 +174,46 @@ impl RawTag {
     /// - non-blocking write if timeout = 0
     #[inline(always)]
     pub fn write(&self, timeout: u32) -> Status {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("plctag.write", tag_id = self.tag_id, timeout).entered();
         let rc = unsafe { ffi::plc_tag_write(self.tag_id, timeout as i32) };
-        rc.into()
+        let status: Status = rc.into();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(status = %status, "write done");
+        status
+    }
+
+    /// perform read operation, returns [`Result`] instead of [`Status`].
+    /// - blocking read if timeout > 0, pending is treated as timeout error
+    /// - non-blocking read if timeout = 0, pending is returned as error
+    #[inline]
+    pub fn read_result(&self, timeout: u32) -> Result<()> {
+        op_result(self.read(timeout), timeout)
+    }
+
+    /// perform read operation, bypassing the read cache configured by `read_cache_ms`.
+    ///
+    /// `read_cache_ms` is set to 0 during the read, then restored.
+    #[inline]
+    pub fn read_fresh(&self, timeout: u32) -> Status {
+        let cache_ms = match self.attr(IntAttr::ReadCacheMs) {
+            Ok(v) if v > 0 => v,
+            _ => return self.read(timeout),
+        };
+        if let Err(e) = self.set_attr(IntAttr::ReadCacheMs.name(), 0) {
+            return e;
+        }
+        let status = self.read(timeout);
+        let _ = self.set_attr(IntAttr::ReadCacheMs.name(), cache_ms);
+        status
+    }
+
+    /// perform write operation, returns [`Result`] instead of [`Status`].
+    /// - blocking write if timeout > 0, pending is treated as timeout error
+    /// - non-blocking write if timeout = 0, pending is returned as error
+    #[inline]
+    pub fn write_result(&self, timeout: u32) -> Result<()> {
+        op_result(self.write(timeout), timeout)
     }
 
     /// wait until not pending, blocking
@@ -126,10 +250,29 @@ impl RawTag {
         self.get_attr("elem_count", 0)
     }
 
+    /// bytes between array elements, read from `elem_size` attribute.
+    /// unlike `size() / elem_count()`, it's not affected by padding at the end of tag data.
+    /// returns `PLCTAG_ERR_UNSUPPORTED` if the tag does not report element size
+    #[inline]
+    pub fn element_stride(&self) -> Result<u32> {
+        match self.elem_size()? {
+            v if v > 0 => Ok(v as u32),
+            _ => Err(Status::Err(ffi::PLCTAG_ERR_UNSUPPORTED)),
+        }
+    }
+
+    /// byte offset of the array element at `index`
+    #[inline]
+    pub fn element_at_offset(&self, index: u32) -> Result<u32> {
+        self.element_stride()?
+            .checked_mul(index)
+            .ok_or(Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS))
+    }
+
     /// get tag attribute
     #[inline(always)]
     pub fn get_attr(&self, attr: impl AsRef<str>, default_value: i32) -> Result<i32> {
-        let attr = CString::new(attr.as_ref()).unwrap();
+        let attr = to_cstring(attr.as_ref())?;
         let val =
             unsafe { ffi::plc_tag_get_int_attribute(self.tag_id, attr.as_ptr(), default_value) };
         if val == i32::MIN {
@@ -142,11 +285,103 @@ impl RawTag {
     /// set tag attribute
     #[inline(always)]
     pub fn set_attr(&self, attr: impl AsRef<str>, value: i32) -> Result<()> {
-        let attr = CString::new(attr.as_ref()).unwrap();
+        let attr = to_cstring(attr.as_ref())?;
         let rc = unsafe { ffi::plc_tag_set_int_attribute(self.tag_id, attr.as_ptr(), value) };
         Status::new(rc).into_result()
     }
 
+    /// get well-known integer attribute
+    #[inline(always)]
+    pub fn attr(&self, attr: IntAttr) -> Result<i32> {
+        self.get_attr(attr.name(), 0)
+    }
+
+    /// typed accessors of `str_*` attributes, which define the string layout of the tag
+    #[inline(always)]
+    pub fn string_attrs(&self) -> StringAttrs<'_> {
+        StringAttrs { tag: self }
+    }
+
+    /// snapshot the tag data buffer, and start setting values in memory.
+    /// see [`Transaction`]
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use plctag_core::{RawTag, ValueExt};
+    /// let timeout = 100;//ms
+    /// let path="protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyUDT&elem_count=1&elem_size=16";
+    /// let tag = RawTag::new(path, timeout).unwrap();
+    /// tag.read(timeout);
+    ///
+    /// let tx = tag.transaction().unwrap();
+    /// tx.set_value(0, 1_u16).unwrap();
+    /// tx.set_value(2, 2_u32).unwrap();
+    /// // tag data buffer is restored if failed to write
+    /// tx.commit(timeout).unwrap();
+    /// ```
+    #[inline]
+    pub fn transaction(&self) -> Result<Transaction<'_>> {
+        let mut snapshot = vec![0; self.size()? as usize];
+        self.get_bytes_unchecked(0, &mut snapshot)?;
+        Ok(Transaction {
+            tag: self,
+            snapshot,
+            done: false,
+        })
+    }
+
+    /// writer skipping writes if the tag data buffer has not changed since last write,
+    /// see [`DiffWriter`]
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use plctag_core::{RawTag, ValueExt};
+    ///
+    /// let timeout = 100;//ms
+    /// let path="protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyTag1&elem_count=1&elem_size=16";
+    /// let tag = RawTag::new(path, timeout).unwrap();
+    ///
+    /// let mut writer = tag.diff_writer();
+    /// writer.set_value(0, 1_u16).unwrap();
+    /// assert!(writer.commit(timeout).unwrap());
+    /// writer.set_value(0, 1_u16).unwrap();
+    /// // nothing changed, no write
+    /// assert!(!writer.commit(timeout).unwrap());
+    /// ```
+    #[inline]
+    pub fn diff_writer(&self) -> DiffWriter<'_> {
+        DiffWriter {
+            tag: self,
+            last: None,
+        }
+    }
+
+    /// issue a non-blocking read, poll the returned [`ReadOp`] for completion.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use plctag_core::RawTag;
+    /// use std::task::Poll;
+    ///
+    /// let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    /// let mut op = tag.begin_read();
+    /// let res = loop {
+    ///     match op.poll() {
+    ///         Poll::Ready(res) => break res,
+    ///         Poll::Pending => {
+    ///             // do other work
+    ///         }
+    ///     }
+    /// };
+    /// ```
+    #[inline]
+    pub fn begin_read(&self) -> ReadOp<'_> {
+        ReadOp {
+            tag: self,
+            status: self.read(0),
+        }
+    }
+
     /// poll tag status
     #[inline(always)]
     pub fn status(&self) -> Status {
@@ -164,9 +399,39 @@ impl RawTag {
         Ok(value as u32)
     }
 
-    /// set tag size in bytes, returns old size
+    /// cache the tag size, which is used by [`RawTag::get_bytes`], [`RawTag::set_bytes`] and
+    /// bounds checks of array accessors, i.e. [`RawTag::get_array`],
+    /// instead of querying `libplctag` on every call, returns the size.
+    ///
+    /// # Note
+    /// the size might change after read, i.e. tags of variable size;
+    /// call [`RawTag::invalidate_size_cache`] to drop the cached size.
+    #[inline]
+    pub fn cache_size(&self) -> Result<u32> {
+        let size = self.size()?;
+        self.size_cache.store(size, Ordering::Relaxed);
+        Ok(size)
+    }
+
+    /// drop the size cached by [`RawTag::cache_size`]
+    #[inline(always)]
+    pub fn invalidate_size_cache(&self) {
+        self.size_cache.store(NO_SIZE, Ordering::Relaxed);
+    }
+
+    /// cached size if any, otherwise query `libplctag`
+    #[inline(always)]
+    fn cached_size(&self) -> Result<u32> {
+        match self.size_cache.load(Ordering::Relaxed) {
+            NO_SIZE => self.size(),
+            size => Ok(size),
+        }
+    }
+
+    /// set tag size in bytes, returns old size; the cached size is dropped
     #[inline(always)]
     pub fn set_size(&self, size: u32) -> Result<u32> {
+        self.invalidate_size_cache();
         let value = unsafe { ffi::plc_tag_set_size(self.tag_id, size as i32) };
         if value < 0 {
             return Err(Status::from(value));
@@ -192,14 +457,36 @@ impl RawTag {
         Status::new(rc).into_result()
     }
 
-    /// get bool value
+    /// get `count` bits from bit offset, i.e. AB `BOOL[32]` which is bit-packed in 4 bytes
+    #[inline]
+    pub fn get_bool_array(&self, bit_offset: u32, count: usize) -> Result<Vec<bool>> {
+        // end of bits must not overflow
+        element_offset(bit_offset, count, 1)?;
+        (0..count as u32)
+            .map(|i| self.get_bit(bit_offset + i))
+            .collect()
+    }
+
+    /// set bits from bit offset, see [`RawTag::get_bool_array`]
+    #[inline]
+    pub fn set_bool_array(&self, bit_offset: u32, values: &[bool]) -> Result<()> {
+        element_offset(bit_offset, values.len(), 1)?;
+        for (i, value) in values.iter().enumerate() {
+            self.set_bit(bit_offset + i as u32, *value)?;
+        }
+        Ok(())
+    }
+
+    /// get bool value, a whole byte for one bool, non-zero is `true`.
+    /// for bit-packed bool arrays, use [`RawTag::get_bool_array`]
     #[inline(always)]
     pub fn get_bool(&self, byte_offset: u32) -> Result<bool> {
         let value = self.get_u8(byte_offset)?;
         Ok(value > 0)
     }
 
-    /// set bool value
+    /// set bool value, a whole byte for one bool.
+    /// for bit-packed bool arrays, use [`RawTag::set_bool_array`]
     #[inline(always)]
     pub fn set_bool(&self, byte_offset: u32, value: bool) -> Result<()> {
         self.set_u8(byte_offset, value as _)
@@ -375,6 +662,20 @@ impl RawTag {
         Status::new(rc).into_result()
     }
 
+    /// get half-precision float value, stored as `u16`
+    #[cfg(feature = "f16")]
+    #[inline(always)]
+    pub fn get_f16(&self, byte_offset: u32) -> Result<half::f16> {
+        self.get_u16(byte_offset).map(half::f16::from_bits)
+    }
+
+    /// set half-precision float value, stored as `u16`
+    #[cfg(feature = "f16")]
+    #[inline(always)]
+    pub fn set_f16(&self, byte_offset: u32, value: half::f16) -> Result<()> {
+        self.set_u16(byte_offset, value.to_bits())
+    }
+
     /// Getting A String Length
     #[inline(always)]
     pub fn get_string_length(&self, byte_offset: u32) -> Result<u32> {
@@ -408,6 +709,31 @@ impl RawTag {
         }
     }
 
+    /// length, capacity and space occupied by the string at byte offset, see [`StringLayout`]
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use plctag_core::RawTag;
+    /// let path="protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyStrings&elem_count=10";
+    /// let tag = RawTag::new(path, 100).unwrap();
+    /// tag.read(100);
+    /// // walk through the array of strings
+    /// let mut offset = 0;
+    /// for _ in 0..10 {
+    ///     let layout = tag.string_layout(offset).unwrap();
+    ///     println!("{}", tag.get_string_owned(offset).unwrap());
+    ///     offset += layout.total_length;
+    /// }
+    /// ```
+    #[inline]
+    pub fn string_layout(&self, byte_offset: u32) -> Result<StringLayout> {
+        Ok(StringLayout {
+            length: self.get_string_length(byte_offset)?,
+            capacity: self.get_string_capacity(byte_offset)?,
+            total_length: self.get_string_total_length(byte_offset)?,
+        })
+    }
+
     /// Reading A String
     #[inline(always)]
     pub fn get_string(&self, byte_offset: u32, buf: &mut [u8]) -> Result<()> {
@@ -422,8 +748,45 @@ impl RawTag {
         Status::new(rc).into_result()
     }
 
+    /// Reading A String into [`String`], invalid UTF-8 sequences are replaced.
+    /// the string layout is defined by tag string attributes
+    #[inline]
+    pub fn get_string_owned(&self, byte_offset: u32) -> Result<String> {
+        let len = self.get_string_length(byte_offset)? as usize;
+        // extra space for the terminating 0 byte
+        let mut buf = vec![0_u8; len + 1];
+        self.get_string(byte_offset, &mut buf)?;
+        buf.truncate(len);
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    /// read `count` strings from byte offset, i.e. AB `STRING[N]`.
+    ///
+    /// each string starts at the offset of previous one plus its total length, see
+    /// [`RawTag::get_string_total_length`]; fixed-stride types report the same total length
+    /// for every string.
+    #[inline]
+    pub fn get_string_array(&self, byte_offset: u32, count: usize) -> Result<Vec<String>> {
+        let mut offset = byte_offset;
+        let mut res = Vec::with_capacity(count);
+        for _ in 0..count {
+            res.push(self.get_string_owned(offset)?);
+            let total_length = self.get_string_total_length(offset)?;
+            if total_length == 0 {
+                // well-formed strings occupy at least the count word or terminator
+                return Err(Status::Err(ffi::PLCTAG_ERR_BAD_DATA));
+            }
+            offset = offset
+                .checked_add(total_length)
+                .ok_or(Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS))?;
+        }
+        Ok(res)
+    }
+
     /// Write A String
-    /// NOTE: panic if buf terminates with 0 byte
+    ///
+    /// # Panics
+    /// panic if buf contains 0 byte, use [`RawTag::try_set_string`] for untrusted input
     #[inline(always)]
     pub fn set_string(&self, byte_offset: u32, buf: impl Into<Vec<u8>>) -> Result<()> {
         let buf = CString::new(buf).unwrap();
@@ -431,6 +794,14 @@ impl RawTag {
         Status::new(rc).into_result()
     }
 
+    /// Write A String, returns `PLCTAG_ERR_BAD_PARAM` if buf contains 0 byte
+    #[inline(always)]
+    pub fn try_set_string(&self, byte_offset: u32, buf: impl Into<Vec<u8>>) -> Result<()> {
+        let buf = to_cstring(buf)?;
+        let rc = unsafe { ffi::plc_tag_set_string(self.tag_id, byte_offset as i32, buf.as_ptr()) };
+        Status::new(rc).into_result()
+    }
+
     /// get raw bytes.
     /// If buffer length would exceed the end of the data in the tag data buffer, an out of bounds error is returned
     #[inline(always)]
@@ -447,13 +818,42 @@ impl RawTag {
         Ok(buf.len())
     }
 
+    /// borrow all bytes of the tag data buffer.
+    ///
+    /// `libplctag` does not expose a pointer to its buffer, the bytes are copied into a
+    /// thread-local scratch buffer that is reused across calls, so no allocation happens
+    /// once the scratch is large enough.
+    ///
+    /// # Note
+    /// the view is a snapshot, changes made by the closure through other tag methods are not reflected.
+    #[inline]
+    pub fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> Result<R> {
+        thread_local! {
+            static SCRATCH: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+        }
+        let size = self.size()? as usize;
+        SCRATCH.with(|scratch| match scratch.try_borrow_mut() {
+            Ok(mut buf) => {
+                buf.resize(size, 0);
+                self.get_bytes_unchecked(0, &mut buf)?;
+                Ok(f(&buf))
+            }
+            Err(_) => {
+                // nested call from the closure
+                let mut buf = vec![0; size];
+                self.get_bytes_unchecked(0, &mut buf)?;
+                Ok(f(&buf))
+            }
+        })
+    }
+
     /// get raw bytes
     #[inline]
     pub fn get_bytes(&self, byte_offset: u32, buf: &mut [u8]) -> Result<usize> {
         if buf.is_empty() {
             return Ok(0);
         }
-        let size = self.size()? as usize;
+        let size = self.cached_size()? as usize;
         if byte_offset as usize >= size {
             return Ok(0);
         }
@@ -463,6 +863,25 @@ impl RawTag {
         self.get_bytes_unchecked(byte_offset, buf)
     }
 
+    /// snapshot of the full tag data buffer, zero-size tags return an empty vec
+    #[inline]
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        let size = self.cached_size()? as usize;
+        let mut buf = vec![0; size];
+        if size > 0 {
+            self.get_bytes_unchecked(0, &mut buf)?;
+        }
+        Ok(buf)
+    }
+
+    /// perform blocking read, then copy raw bytes from offset 0 into `buf`, see [`RawTag::read_result`]
+    /// and [`RawTag::get_bytes`]. returns the number of bytes copied
+    #[inline]
+    pub fn read_bytes(&self, timeout: u32, buf: &mut [u8]) -> Result<usize> {
+        self.read_result(timeout)?;
+        self.get_bytes(0, buf)
+    }
+
     /// set raw bytes.
     /// If buffer length would exceed the end of the data in the tag data buffer, an out of bounds error is returned
     #[inline(always)]
@@ -485,7 +904,7 @@ impl RawTag {
         if buf.is_empty() {
             return Ok(0);
         }
-        let size = self.size()? as usize;
+        let size = self.cached_size()? as usize;
         if byte_offset as usize >= size {
             return Ok(0);
         }
@@ -495,6 +914,162 @@ impl RawTag {
         self.set_bytes_unchecked(byte_offset, buf)
     }
 
+    /// set value in memory, then perform blocking write, see [`RawTag::write_result`]
+    #[cfg(feature = "value")]
+    #[inline]
+    pub fn set_and_write<T: Encode>(&self, byte_offset: u32, value: T, timeout: u32) -> Result<()> {
+        value.encode(self, byte_offset)?;
+        self.write_result(timeout)
+    }
+
+    /// perform blocking read, then get value from memory, see [`RawTag::read_result`]
+    #[cfg(feature = "value")]
+    #[inline]
+    pub fn read_and_get<T: Decode>(&self, byte_offset: u32, timeout: u32) -> Result<T> {
+        self.read_result(timeout)?;
+        T::decode(self, byte_offset)
+    }
+
+    /// get `count` elements of `T` from byte offset, elements are `T::SIZE` bytes apart.
+    /// returns out of bounds error if the elements exceed the end of tag data buffer,
+    /// bad param error if `T::SIZE` is 0, i.e. `String`
+    #[cfg(feature = "value")]
+    #[inline]
+    pub fn get_array<T: Decode>(&self, byte_offset: u32, count: usize) -> Result<Vec<T>> {
+        self.check_bounds(byte_offset, count, T::SIZE)?;
+        let mut res = Vec::with_capacity(count);
+        for i in 0..count {
+            res.push(T::decode(self, byte_offset + i as u32 * T::SIZE)?);
+        }
+        Ok(res)
+    }
+
+    /// iterate elements `0..elem_count` of `T`, elements are [`RawTag::element_stride`] bytes apart.
+    /// the iteration stops after yielding the first error; it yields bad param error if `T::SIZE`
+    /// is 0 or larger than the stride.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use plctag_core::RawTag;
+    /// let path="protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyArray&elem_count=10";
+    /// let tag = RawTag::new(path, 100).unwrap();
+    /// tag.read(100);
+    /// for v in tag.iter_values::<i32>() {
+    ///     println!("{}", v.unwrap());
+    /// }
+    /// ```
+    #[cfg(feature = "value")]
+    #[inline]
+    pub fn iter_values<T: Decode>(&self) -> ValueIter<'_, T> {
+        let (count, stride, error) = match self.element_layout::<T>() {
+            Ok((count, stride)) => (count, stride, None),
+            Err(e) => (0, 0, Some(e)),
+        };
+        ValueIter {
+            tag: self,
+            index: 0,
+            count,
+            stride,
+            error,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// decode `buf.len()` elements of `T` from byte offset into `buf` in place,
+    /// elements are `T::SIZE` bytes apart. no allocation happens.
+    /// returns out of bounds error if the elements exceed the end of tag data buffer,
+    /// bad param error if `T::SIZE` is 0
+    #[cfg(feature = "value")]
+    #[inline]
+    pub fn get_values_into<T: Decode>(&self, byte_offset: u32, buf: &mut [T]) -> Result<()> {
+        self.check_bounds(byte_offset, buf.len(), T::SIZE)?;
+        for (i, place) in buf.iter_mut().enumerate() {
+            T::decode_in_place(self, byte_offset + i as u32 * T::SIZE, place)?;
+        }
+        Ok(())
+    }
+
+    /// set elements of `T` from byte offset, elements are `T::SIZE` bytes apart.
+    /// returns out of bounds error if the elements exceed the end of tag data buffer,
+    /// bad param error if `T::SIZE` is 0, i.e. `String`, `&str`
+    #[cfg(feature = "value")]
+    #[inline]
+    pub fn set_array<T: Encode>(&self, byte_offset: u32, values: &[T]) -> Result<()> {
+        self.check_bounds(byte_offset, values.len(), T::SIZE)?;
+        for (i, v) in values.iter().enumerate() {
+            v.encode(self, byte_offset + i as u32 * T::SIZE)?;
+        }
+        Ok(())
+    }
+
+    /// get all `elem_count` elements of `T` in the tag, elements are
+    /// [`RawTag::element_stride`] bytes apart like [`RawTag::iter_values`].
+    /// returns out of bounds error if the elements exceed the end of tag data buffer,
+    /// bad param error if `T::SIZE` is 0 or larger than the stride,
+    /// `PLCTAG_ERR_UNSUPPORTED` if the tag does not report element size
+    #[cfg(feature = "value")]
+    #[inline]
+    pub fn get_all<T: Decode>(&self) -> Result<Vec<T>> {
+        let (count, stride) = self.element_layout::<T>()?;
+        self.check_bounds(0, count as usize, stride)?;
+        self.iter_values().collect()
+    }
+
+    /// `(elem_count, element_stride)` of the tag for elements of `T`
+    #[cfg(feature = "value")]
+    #[inline]
+    fn element_layout<T: Decode>(&self) -> Result<(u32, u32)> {
+        let count = self.elem_count()?.max(0) as u32;
+        let stride = self.element_stride()?;
+        // variable sized values or values larger than the stride would overlap each other
+        if T::SIZE == 0 || T::SIZE > stride {
+            return Err(Status::Err(ffi::PLCTAG_ERR_BAD_PARAM));
+        }
+        Ok((count, stride))
+    }
+
+    #[cfg(feature = "value")]
+    #[inline]
+    fn check_bounds(&self, byte_offset: u32, count: usize, stride: u32) -> Result<()> {
+        // variable sized values would overlap each other
+        if stride == 0 {
+            return Err(Status::Err(ffi::PLCTAG_ERR_BAD_PARAM));
+        }
+        let end = byte_offset as u64 + count as u64 * stride as u64;
+        if end > self.cached_size()? as u64 {
+            return Err(Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS));
+        }
+        Ok(())
+    }
+
+    /// deserialize `len` bytes from byte offset with `serde`.
+    /// bytes are decoded by `bincode` with little-endian and fixed size integer encoding,
+    /// which fits fixed C layout of UDTs.
+    #[cfg(feature = "serde")]
+    pub fn get_serde<T: serde::de::DeserializeOwned>(
+        &self,
+        byte_offset: u32,
+        len: usize,
+    ) -> Result<T> {
+        use bincode::Options;
+        let mut buf = vec![0; len];
+        self.get_bytes_unchecked(byte_offset, &mut buf)?;
+        bincode_options()
+            .deserialize(&buf)
+            .map_err(|_| Status::Err(ffi::PLCTAG_ERR_BAD_DATA))
+    }
+
+    /// serialize value with `serde`, then set the bytes at byte offset.
+    /// see [`RawTag::get_serde`] for the encoding.
+    #[cfg(feature = "serde")]
+    pub fn set_serde<T: serde::Serialize>(&self, byte_offset: u32, value: &T) -> Result<usize> {
+        use bincode::Options;
+        let buf = bincode_options()
+            .serialize(value)
+            .map_err(|_| Status::Err(ffi::PLCTAG_ERR_ENCODE))?;
+        self.set_bytes_unchecked(byte_offset, &buf)
+    }
+
     /// note: registering a new callback will override existing one
     ///
     /// # Safety
@@ -504,6 +1079,17 @@ impl RawTag {
     pub unsafe fn register_callback(
         &self,
         cb: Option<unsafe extern "C" fn(tag_id: i32, event: i32, status: i32)>,
+    ) -> Status {
+        let status = self.replace_callback(cb);
+        self.raw_callback_replaced(cb.is_some() && status.is_ok());
+        status
+    }
+
+    /// register the callback without tracking it as raw callback
+    #[inline]
+    pub(crate) unsafe fn replace_callback(
+        &self,
+        cb: Option<unsafe extern "C" fn(tag_id: i32, event: i32, status: i32)>,
     ) -> Status {
         //unregister first
         let _ = ffi::plc_tag_unregister_callback(self.tag_id);
@@ -511,6 +1097,31 @@ impl RawTag {
         rc.into()
     }
 
+    /// the raw callback overrides handlers registered by `RawTag::on_event`
+    #[inline(always)]
+    fn raw_callback_replaced(&self, _alive: bool) {
+        #[cfg(feature = "callback")]
+        {
+            self.raw_callback.store(_alive, Ordering::Relaxed);
+            crate::event::remove_handler(self.tag_id);
+        }
+    }
+
+    /// is a callback registered by [`RawTag::register_callback`], [`RawTag::register_callback_ex`]
+    /// or [`RawTag::new_with_callback`] alive?
+    #[cfg(feature = "callback")]
+    #[inline(always)]
+    pub(crate) fn has_raw_callback(&self) -> bool {
+        self.raw_callback.load(Ordering::Relaxed)
+    }
+
+    /// forget the raw callback, which has been unregistered or overridden
+    #[inline(always)]
+    pub(crate) fn clear_raw_callback(&self) {
+        #[cfg(feature = "callback")]
+        self.raw_callback.store(false, Ordering::Relaxed);
+    }
+
     /// note: registering a new callback will override existing one
     ///
     /// # Safety
@@ -527,13 +1138,16 @@ impl RawTag {
         //unregister first
         let _ = ffi::plc_tag_unregister_callback(self.tag_id);
         let rc = ffi::plc_tag_register_callback_ex(self.tag_id, cb, user_data);
-        rc.into()
+        let status: Status = rc.into();
+        self.raw_callback_replaced(cb.is_some() && status.is_ok());
+        status
     }
 
     /// unregister the callback
     #[inline]
     pub fn unregister_callback(&self) -> Status {
         let rc = unsafe { ffi::plc_tag_unregister_callback(self.tag_id) };
+        self.clear_raw_callback();
         rc.into()
     }
 
@@ -546,49 +1160,519 @@ impl RawTag {
         let rc = unsafe { ffi::plc_tag_abort(self.tag_id) };
         Status::new(rc).into_result()
     }
+
+    /// abort the pending operation if any, returns whether the operation was aborted
+    #[inline]
+    pub fn abort_if_pending(&self) -> Result<bool> {
+        if self.status().is_pending() {
+            self.abort()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
 }
 
-impl Drop for RawTag {
-    #[inline(always)]
-    fn drop(&mut self) {
-        unsafe {
-            //let _ = self.abort();
-            ffi::plc_tag_destroy(self.tag_id);
+/// well-known integer attributes of tag or library
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IntAttr {
+    /// `elem_size`: element size in bytes
+    ElemSize,
+    /// `elem_count`: element count
+    ElemCount,
+    /// `size`: tag size in bytes
+    Size,
+    /// `read_cache_ms`
+    ReadCacheMs,
+    /// `auto_sync_read_ms`
+    AutoSyncReadMs,
+    /// `auto_sync_write_ms`
+    AutoSyncWriteMs,
+    /// `version_major`, library attribute
+    VersionMajor,
+    /// `version_minor`, library attribute
+    VersionMinor,
+    /// `version_patch`, library attribute
+    VersionPatch,
+    /// `debug`, library attribute
+    Debug,
+}
+
+impl IntAttr {
+    /// attribute name
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        match self {
+            IntAttr::ElemSize => "elem_size",
+            IntAttr::ElemCount => "elem_count",
+            IntAttr::Size => "size",
+            IntAttr::ReadCacheMs => "read_cache_ms",
+            IntAttr::AutoSyncReadMs => "auto_sync_read_ms",
+            IntAttr::AutoSyncWriteMs => "auto_sync_write_ms",
+            IntAttr::VersionMajor => "version_major",
+            IntAttr::VersionMinor => "version_minor",
+            IntAttr::VersionPatch => "version_patch",
+            IntAttr::Debug => "debug",
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// version of `libplctag`: (major, minor, patch), parts not available are 0
+#[inline]
+pub fn library_version() -> (i32, i32, i32) {
+    let get = |attr: IntAttr| get_global_attr(attr.name(), 0).unwrap_or(0);
+    (
+        get(IntAttr::VersionMajor),
+        get(IntAttr::VersionMinor),
+        get(IntAttr::VersionPatch),
+    )
+}
 
-    #[test]
-    fn test_debug() {
-        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+/// release all global resources of `libplctag`, i.e. before unloading the library.
+///
+/// # Note
+/// all existing [`RawTag`]s are destroyed and become invalid afterward, drop them before calling this.
+/// the library is initialized again once a new tag is created.
+#[inline]
+pub fn shutdown() {
+    unsafe { ffi::plc_tag_shutdown() };
+}
 
-        let size = tag.size().unwrap();
-        assert!(size > 0);
+/// get library-global attribute, i.e. `debug`, `version_major`
+///
+/// library attributes are on tag id 0, no tag needed.
+///
+/// # Errors
+/// - `PLCTAG_ERR_BAD_PARAM` if `attr` contains 0 byte
+/// - `PLCTAG_ERR_UNSUPPORTED` if the library returns `i32::MIN`, i.e. unknown attribute;
+///   no reason is reported, id 0 is not a tag so it has no status to query
+#[inline]
+pub fn get_global_attr(attr: impl AsRef<str>, default_value: i32) -> Result<i32> {
+    let attr = to_cstring(attr.as_ref())?;
+    let val = unsafe { ffi::plc_tag_get_int_attribute(0, attr.as_ptr(), default_value) };
+    if val == i32::MIN {
+        return Err(Status::Err(ffi::PLCTAG_ERR_UNSUPPORTED));
+    }
+    Ok(val)
+}
 
-        //read
-        let res = tag.read(100);
-        assert!(res.is_ok());
-        let level = tag.get_u32(0).unwrap_or_default();
-        assert_eq!(level, 4);
+/// set library-global attribute, i.e. `debug`, see [`get_global_attr`]
+#[inline]
+pub fn set_global_attr(attr: impl AsRef<str>, value: i32) -> Result<()> {
+    let attr = to_cstring(attr.as_ref())?;
+    let rc = unsafe { ffi::plc_tag_set_int_attribute(0, attr.as_ptr(), value) };
+    Status::new(rc).into_result()
+}
 
-        //write
-        let res = tag.set_u32(0, 1);
-        assert!(res.is_ok());
-        let res = tag.write(100);
-        assert!(res.is_ok());
+#[cfg(feature = "serde")]
+#[inline(always)]
+fn bincode_options() -> impl bincode::Options {
+    use bincode::Options;
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_little_endian()
+}
 
-        //read
-        let res = tag.read(100);
-        assert!(res.is_ok());
-        let level = tag.get_u32(0).unwrap_or_default();
-        assert_eq!(level, 1);
+/// offset of element `index` from `base`, `PLCTAG_ERR_OUT_OF_BOUNDS` if overflows
+#[inline(always)]
+pub(crate) fn element_offset(base: u32, index: usize, stride: u32) -> Result<u32> {
+    u32::try_from(index)
+        .ok()
+        .and_then(|index| index.checked_mul(stride))
+        .and_then(|offset| offset.checked_add(base))
+        .ok_or(Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS))
+}
 
-        let mut buf: Vec<u8> = vec![0; size as usize];
-        let size = tag.get_bytes(0, &mut buf).unwrap();
+/// `PLCTAG_ERR_BAD_PARAM` if contains 0 byte
+#[inline(always)]
+fn to_cstring(s: impl Into<Vec<u8>>) -> Result<CString> {
+    CString::new(s).map_err(|_| Status::Err(ffi::PLCTAG_ERR_BAD_PARAM))
+}
+
+#[inline(always)]
+fn op_result(status: Status, timeout: u32) -> Result<()> {
+    match status {
+        Status::Pending if timeout > 0 => Err(Status::Err(ffi::PLCTAG_ERR_TIMEOUT)),
+        _ => status.into_result(),
+    }
+}
+
+/// iterator of tag elements, see [`RawTag::iter_values`]
+#[cfg(feature = "value")]
+#[derive(Debug)]
+pub struct ValueIter<'a, T> {
+    tag: &'a RawTag,
+    index: u32,
+    count: u32,
+    stride: u32,
+    error: Option<Status>,
+    _marker: core::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "value")]
+impl<T: Decode> Iterator for ValueIter<'_, T> {
+    type Item = Result<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.error.take() {
+            return Some(Err(e));
+        }
+        if self.index >= self.count {
+            return None;
+        }
+        let res = element_offset(0, self.index as usize, self.stride)
+            .and_then(|offset| T::decode(self.tag, offset));
+        self.index = if res.is_ok() {
+            self.index + 1
+        } else {
+            self.count
+        };
+        Some(res)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.count - self.index) as usize + self.error.is_some() as usize;
+        (n, Some(n))
+    }
+}
+
+#[cfg(feature = "value")]
+impl<T: Decode> core::iter::FusedIterator for ValueIter<'_, T> {}
+
+/// layout of a string in the tag data buffer, see [`RawTag::string_layout`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct StringLayout {
+    /// number of characters of the string
+    pub length: u32,
+    /// max number of characters the string can hold
+    pub capacity: u32,
+    /// bytes occupied by the string, including count word, terminating 0 byte and padding.
+    /// the next string in an array starts at offset + `total_length`
+    pub total_length: u32,
+}
+
+/// typed accessors of `str_*` tag attributes
+///
+/// # Tag String Attributes
+/// See https://github.com/libplctag/libplctag/wiki/Tag-String-Attributes for string attributes.
+#[derive(Debug)]
+pub struct StringAttrs<'a> {
+    tag: &'a RawTag,
+}
+
+impl StringAttrs<'_> {
+    #[inline(always)]
+    fn get_bool(&self, attr: &str) -> Result<bool> {
+        Ok(self.tag.get_attr(attr, 0)? != 0)
+    }
+
+    #[inline(always)]
+    fn get_u32(&self, attr: &str) -> Result<u32> {
+        Ok(self.tag.get_attr(attr, 0)? as u32)
+    }
+
+    /// `str_is_counted`: string has a count word
+    #[inline]
+    pub fn is_counted(&self) -> Result<bool> {
+        self.get_bool("str_is_counted")
+    }
+
+    /// set `str_is_counted`
+    #[inline]
+    pub fn set_counted(&self, yes: bool) -> Result<()> {
+        self.tag.set_attr("str_is_counted", yes as i32)
+    }
+
+    /// `str_is_fixed_length`: string occupies a fixed length
+    #[inline]
+    pub fn is_fixed_length(&self) -> Result<bool> {
+        self.get_bool("str_is_fixed_length")
+    }
+
+    /// set `str_is_fixed_length`
+    #[inline]
+    pub fn set_fixed_length(&self, yes: bool) -> Result<()> {
+        self.tag.set_attr("str_is_fixed_length", yes as i32)
+    }
+
+    /// `str_is_zero_terminated`: string terminates with 0 byte
+    #[inline]
+    pub fn is_zero_terminated(&self) -> Result<bool> {
+        self.get_bool("str_is_zero_terminated")
+    }
+
+    /// set `str_is_zero_terminated`
+    #[inline]
+    pub fn set_zero_terminated(&self, yes: bool) -> Result<()> {
+        self.tag.set_attr("str_is_zero_terminated", yes as i32)
+    }
+
+    /// `str_is_byte_swapped`: bytes of characters are swapped in pairs
+    #[inline]
+    pub fn is_byte_swapped(&self) -> Result<bool> {
+        self.get_bool("str_is_byte_swapped")
+    }
+
+    /// set `str_is_byte_swapped`
+    #[inline]
+    pub fn set_byte_swapped(&self, yes: bool) -> Result<()> {
+        self.tag.set_attr("str_is_byte_swapped", yes as i32)
+    }
+
+    /// `str_count_word_bytes`: size in bytes of the count word
+    #[inline]
+    pub fn count_word_bytes(&self) -> Result<u32> {
+        self.get_u32("str_count_word_bytes")
+    }
+
+    /// set `str_count_word_bytes`
+    #[inline]
+    pub fn set_count_word_bytes(&self, size: u32) -> Result<()> {
+        self.tag.set_attr("str_count_word_bytes", size as i32)
+    }
+
+    /// `str_max_capacity`: max number of characters
+    #[inline]
+    pub fn max_capacity(&self) -> Result<u32> {
+        self.get_u32("str_max_capacity")
+    }
+
+    /// set `str_max_capacity`
+    #[inline]
+    pub fn set_max_capacity(&self, capacity: u32) -> Result<()> {
+        self.tag.set_attr("str_max_capacity", capacity as i32)
+    }
+
+    /// `str_total_length`: total bytes occupied by a string, including count word and padding
+    #[inline]
+    pub fn total_length(&self) -> Result<u32> {
+        self.get_u32("str_total_length")
+    }
+
+    /// set `str_total_length`
+    #[inline]
+    pub fn set_total_length(&self, size: u32) -> Result<()> {
+        self.tag.set_attr("str_total_length", size as i32)
+    }
+
+    /// `str_pad_bytes`: padding bytes after the characters
+    #[inline]
+    pub fn pad_bytes(&self) -> Result<u32> {
+        self.get_u32("str_pad_bytes")
+    }
+
+    /// set `str_pad_bytes`
+    #[inline]
+    pub fn set_pad_bytes(&self, size: u32) -> Result<()> {
+        self.tag.set_attr("str_pad_bytes", size as i32)
+    }
+}
+
+/// in-memory changes of tag data buffer that are restored unless written to PLC.
+///
+/// - [`Transaction::commit`] performs write, the buffer is restored if write failed
+/// - dropping without commit restores the buffer
+///
+/// # Note
+/// the snapshot is taken client side, it does not make the write atomic in PLC controller.
+#[derive(Debug)]
+#[must_use = "changes are rolled back if not committed"]
+pub struct Transaction<'a> {
+    tag: &'a RawTag,
+    snapshot: Vec<u8>,
+    done: bool,
+}
+
+impl Transaction<'_> {
+    /// blocking write, restores the tag data buffer if failed
+    #[inline]
+    pub fn commit(mut self, timeout: u32) -> Result<()> {
+        self.done = true;
+        let res = self.tag.write_result(timeout);
+        if res.is_err() {
+            self.restore();
+        }
+        res
+    }
+
+    /// discard the changes, restores the tag data buffer
+    #[inline]
+    pub fn rollback(self) {}
+
+    #[inline(always)]
+    fn restore(&self) {
+        let _ = self.tag.set_bytes_unchecked(0, &self.snapshot);
+    }
+}
+
+impl core::ops::Deref for Transaction<'_> {
+    type Target = RawTag;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.tag
+    }
+}
+
+impl Drop for Transaction<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.done {
+            self.restore();
+        }
+    }
+}
+
+/// keeps the last written tag data buffer, to skip writing unchanged data, see [`RawTag::diff_writer`]
+///
+/// # Note
+/// `libplctag` does not support partial writes, the whole buffer is written if any byte changed;
+/// [`DiffWriter::dirty_range`] tells the changed bytes.
+#[derive(Debug)]
+pub struct DiffWriter<'a> {
+    tag: &'a RawTag,
+    last: Option<Vec<u8>>,
+}
+
+impl DiffWriter<'_> {
+    /// range of bytes changed since last write, the whole buffer if never written,
+    /// `None` if nothing changed
+    #[inline]
+    pub fn dirty_range(&self) -> Result<Option<core::ops::Range<usize>>> {
+        let current = self.tag.to_vec()?;
+        Ok(diff_range(self.last.as_deref(), &current))
+    }
+
+    /// blocking write if the buffer changed since last write, returns whether written
+    #[inline]
+    pub fn commit(&mut self, timeout: u32) -> Result<bool> {
+        let current = self.tag.to_vec()?;
+        if diff_range(self.last.as_deref(), &current).is_none() {
+            return Ok(false);
+        }
+        self.tag.write_result(timeout)?;
+        self.last = Some(current);
+        Ok(true)
+    }
+
+    /// forget the last written buffer, the next commit always writes
+    #[inline]
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+impl core::ops::Deref for DiffWriter<'_> {
+    type Target = RawTag;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.tag
+    }
+}
+
+/// minimal range of different bytes
+#[inline]
+fn diff_range(last: Option<&[u8]>, current: &[u8]) -> Option<core::ops::Range<usize>> {
+    let last = match last {
+        Some(v) => v,
+        None => return Some(0..current.len()),
+    };
+    if last.len() != current.len() {
+        return Some(0..current.len());
+    }
+    let start = last.iter().zip(current).position(|(a, b)| a != b)?;
+    let end = last.iter().zip(current).rposition(|(a, b)| a != b)? + 1;
+    Some(start..end)
+}
+
+/// pending non-blocking read, see [`RawTag::begin_read`]
+///
+/// the read is aborted if still pending when dropped.
+#[derive(Debug)]
+#[must_use = "the read is aborted if the op drops while pending"]
+pub struct ReadOp<'a> {
+    tag: &'a RawTag,
+    status: Status,
+}
+
+impl ReadOp<'_> {
+    /// check whether the read completed
+    #[inline]
+    pub fn poll(&mut self) -> core::task::Poll<Result<()>> {
+        if self.status.is_pending() {
+            self.status = self.tag.status();
+        }
+        if self.status.is_pending() {
+            core::task::Poll::Pending
+        } else {
+            core::task::Poll::Ready(self.status.into_result())
+        }
+    }
+
+    /// abort the read if still pending
+    #[inline]
+    pub fn abort(mut self) -> Result<()> {
+        if self.status.is_pending() {
+            self.status = Status::Err(ffi::PLCTAG_ERR_ABORT);
+            self.tag.abort()?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ReadOp<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.status.is_pending() {
+            let _ = self.tag.abort();
+        }
+    }
+}
+
+impl Drop for RawTag {
+    #[inline(always)]
+    fn drop(&mut self) {
+        unsafe {
+            //let _ = self.abort();
+            ffi::plc_tag_destroy(self.tag_id);
+        }
+        #[cfg(feature = "callback")]
+        crate::event::remove_handler(self.tag_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+
+        let size = tag.size().unwrap();
+        assert!(size > 0);
+
+        //read
+        let res = tag.read(100);
+        assert!(res.is_ok());
+        let level = tag.get_u32(0).unwrap_or_default();
+        assert_eq!(level, 4);
+
+        //write
+        let res = tag.set_u32(0, 1);
+        assert!(res.is_ok());
+        let res = tag.write(100);
+        assert!(res.is_ok());
+
+        //read
+        let res = tag.read(100);
+        assert!(res.is_ok());
+        let level = tag.get_u32(0).unwrap_or_default();
+        assert_eq!(level, 1);
+
+        let mut buf: Vec<u8> = vec![0; size as usize];
+        let size = tag.get_bytes(0, &mut buf).unwrap();
         assert_eq!(size, 30);
         let result = &[
             1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -605,4 +1689,571 @@ mod tests {
         let result = &[3, 0, 0];
         assert_eq!(&buf[0..3], result);
     }
+
+    #[cfg(feature = "value")]
+    #[test]
+    fn test_get_all() {
+        let tag = RawTag::new(
+            "make=system&family=library&name=debug&debug=4&elem_count=4",
+            100,
+        )
+        .unwrap();
+        let stride = tag.element_stride().unwrap();
+        let count = tag.elem_count().unwrap() as u32;
+        for i in 0..count {
+            tag.set_u8(i * stride, i as u8 + 1).unwrap();
+        }
+        let values: Vec<u8> = tag.get_all().unwrap();
+        assert_eq!(values.len(), count as usize);
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(*v, i as u8 + 1);
+        }
+
+        // elements larger than the stride
+        assert!(stride < 8);
+        let res = tag.get_all::<u64>();
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        // zero sized elements
+        let res = tag.get_all::<String>();
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    #[cfg(feature = "value")]
+    #[test]
+    fn test_get_array() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let size = tag.size().unwrap();
+
+        let bytes: Vec<u8> = tag.get_array(0, size as usize).unwrap();
+        assert_eq!(bytes.len(), size as usize);
+
+        tag.set_array(0, &[1_u8, 2, 3]).unwrap();
+        let values: Vec<u8> = tag.get_array(0, 3).unwrap();
+        assert_eq!(values, [1, 2, 3]);
+
+        let res = tag.get_array::<u8>(1, size as usize);
+        assert!(res.is_err());
+
+        // zero sized elements
+        let res = tag.get_array::<String>(0, 2);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        let res = tag.set_array(0, &["a", "b"]);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        let mut buf = vec![String::new(); 2];
+        let res = tag.get_values_into(0, &mut buf);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    #[test]
+    fn test_string_attrs() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let attrs = tag.string_attrs();
+        attrs.set_max_capacity(82).unwrap();
+        assert_eq!(attrs.max_capacity().unwrap(), 82);
+    }
+
+    #[test]
+    fn test_read_write_result() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        tag.read_result(100).unwrap();
+        tag.write_result(100).unwrap();
+
+        let res = op_result(Status::Pending, 100);
+        assert!(res.unwrap_err().is_timeout());
+        let res = op_result(Status::Pending, 0);
+        assert!(res.unwrap_err().is_pending());
+    }
+
+    #[test]
+    fn test_library_version() {
+        let version = library_version();
+        assert_ne!(version, (0, 0, 0));
+
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let size = tag.attr(IntAttr::Size).unwrap();
+        assert_eq!(size as u32, tag.size().unwrap());
+    }
+
+    #[cfg(feature = "builder")]
+    #[test]
+    fn test_from_builder() {
+        use crate::builder::*;
+
+        let mut builder = PathBuilder::default();
+        builder
+            .protocol(Protocol::EIP)
+            .gateway("192.168.1.120")
+            .plc(PlcKind::ControlLogix)
+            .name("MyTag1")
+            .path("1,0");
+        // non-blocking creation
+        let tag = RawTag::from_builder(&builder, 0).unwrap();
+        assert!(tag.id() > 0);
+
+        let res = RawTag::from_builder(&PathBuilder::default(), 0);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        #[repr(C)]
+        struct Pair {
+            a: u16,
+            b: u16,
+        }
+
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        tag.set_bytes(0, &[1, 0, 2, 0]).unwrap();
+        let pair: Pair = tag.get_serde(0, 4).unwrap();
+        assert_eq!(pair, Pair { a: 1, b: 2 });
+
+        let count = tag.set_serde(0, &Pair { a: 3, b: 4 }).unwrap();
+        assert_eq!(count, 4);
+        assert_eq!(tag.get_u16(2).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_get_string_owned() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        // 1 byte count word followed by up to 20 characters
+        let attrs = tag.string_attrs();
+        attrs.set_counted(true).unwrap();
+        attrs.set_count_word_bytes(1).unwrap();
+        attrs.set_max_capacity(20).unwrap();
+        attrs.set_total_length(21).unwrap();
+
+        tag.try_set_string(0, "hello").unwrap();
+        assert_eq!(tag.get_u8(0).unwrap(), 5);
+        assert_eq!(tag.get_string_owned(0).unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_try_set_string() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let res = tag.try_set_string(0, "a\0b");
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    #[test]
+    fn test_nul_param() {
+        let res = RawTag::new("make=system&family=library&name=debug\0&debug=4", 100);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let res = tag.get_attr("elem\0size", 0);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        let res = tag.set_attr("elem\0size", 0);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    #[test]
+    fn test_element_at_offset() {
+        let tag = RawTag::new(
+            "make=system&family=library&name=debug&debug=4&elem_count=4",
+            100,
+        )
+        .unwrap();
+        let elem_size = tag.elem_size().unwrap() as u32;
+        assert_eq!(tag.element_stride().unwrap(), elem_size);
+        assert_eq!(tag.element_at_offset(0).unwrap(), 0);
+        assert_eq!(tag.element_at_offset(2).unwrap(), 2 * elem_size);
+    }
+
+    #[test]
+    fn test_try_clone() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let tag2 = tag.try_clone(100).unwrap();
+        assert_ne!(tag.id(), tag2.id());
+
+        // independent buffers
+        tag.set_u8(0, 1).unwrap();
+        tag2.set_u8(0, 2).unwrap();
+        assert_eq!(tag.get_u8(0).unwrap(), 1);
+        assert_eq!(tag2.get_u8(0).unwrap(), 2);
+
+        assert!(tag.read(100).is_ok());
+        assert!(tag2.read(100).is_ok());
+    }
+
+    #[test]
+    fn test_is_connected() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        assert!(tag.is_connected());
+    }
+
+    #[test]
+    fn test_transaction() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        tag.set_u8(0, 1).unwrap();
+        {
+            let tx = tag.transaction().unwrap();
+            tx.set_u8(0, 2).unwrap();
+            assert_eq!(tx.get_u8(0).unwrap(), 2);
+        }
+        // not committed
+        assert_eq!(tag.get_u8(0).unwrap(), 1);
+
+        let tx = tag.transaction().unwrap();
+        tx.set_u8(0, 4).unwrap();
+        tx.commit(100).unwrap();
+        assert_eq!(tag.get_u8(0).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_transaction_write_failed() {
+        // version tag is read only
+        let tag = RawTag::new("make=system&family=library&name=version", 100).unwrap();
+        assert!(tag.read(100).is_ok());
+        let value = tag.get_u8(0).unwrap();
+
+        let tx = tag.transaction().unwrap();
+        tx.set_u8(0, value.wrapping_add(1)).unwrap();
+        assert!(tx.commit(100).is_err());
+        assert_eq!(tag.get_u8(0).unwrap(), value);
+    }
+
+    #[test]
+    fn test_read_fresh() {
+        let tag = RawTag::new(
+            "make=system&family=library&name=debug&debug=4&read_cache_ms=1000",
+            100,
+        )
+        .unwrap();
+        assert_eq!(tag.attr(IntAttr::ReadCacheMs).unwrap(), 1000);
+        assert!(tag.read_fresh(100).is_ok());
+        assert_eq!(tag.attr(IntAttr::ReadCacheMs).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_with_bytes() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let size = tag.size().unwrap() as usize;
+        for i in 0..size {
+            tag.set_u8(i as u32, i as u8).unwrap();
+        }
+        let mut buf = vec![0; size];
+        tag.get_bytes(0, &mut buf).unwrap();
+
+        let bytes = tag.with_bytes(|bytes| bytes.to_vec()).unwrap();
+        assert_eq!(bytes, buf);
+
+        // nested
+        let res = tag
+            .with_bytes(|outer| tag.with_bytes(|inner| outer == inner).unwrap())
+            .unwrap();
+        assert!(res);
+    }
+
+    #[cfg(feature = "value")]
+    #[test]
+    fn test_set_and_write() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        tag.set_and_write(0, 4_u32, 100).unwrap();
+        let level: u32 = tag.read_and_get(0, 100).unwrap();
+        assert_eq!(level, 4);
+    }
+
+    #[test]
+    fn test_string_layout() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let attrs = tag.string_attrs();
+        attrs.set_counted(true).unwrap();
+        attrs.set_count_word_bytes(1).unwrap();
+        attrs.set_max_capacity(20).unwrap();
+        attrs.set_total_length(21).unwrap();
+        tag.try_set_string(0, "hello").unwrap();
+
+        let layout = tag.string_layout(0).unwrap();
+        assert_eq!(layout.length, 5);
+        assert!(layout.capacity >= layout.length);
+        assert!(layout.total_length >= layout.length);
+    }
+
+    #[cfg(feature = "value")]
+    #[test]
+    fn test_get_values_into() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        for i in 0..4 {
+            tag.set_u16(2 + i * 2, i as u16 + 10).unwrap();
+        }
+        let mut buf = [0_u16; 4];
+        tag.get_values_into(2, &mut buf).unwrap();
+        for (i, v) in buf.iter().enumerate() {
+            assert_eq!(*v, tag.get_u16(2 + i as u32 * 2).unwrap());
+        }
+
+        let size = tag.size().unwrap();
+        let res = tag.get_values_into(size - 4, &mut buf);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+    }
+
+    #[test]
+    fn test_abort_if_pending() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        assert!(tag.read(100).is_ok());
+        assert!(!tag.abort_if_pending().unwrap());
+    }
+
+    #[test]
+    fn test_send_sync() {
+        use std::sync::{Arc, Mutex};
+
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<RawTag>();
+        assert_send_sync::<Arc<Mutex<RawTag>>>();
+
+        let tag = Arc::new(Mutex::new(
+            RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap(),
+        ));
+        let handle = {
+            let tag = tag.clone();
+            thread::spawn(move || tag.lock().unwrap().read(100))
+        };
+        assert!(handle.join().unwrap().is_ok());
+        assert!(tag.lock().unwrap().status().is_ok());
+    }
+
+    #[test]
+    fn test_bool_array() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let mut bits = [false; 16];
+        bits[0] = true;
+        bits[9] = true;
+        bits[15] = true;
+        tag.set_bool_array(8, &bits).unwrap();
+        assert_eq!(tag.get_u8(1).unwrap(), 0b0000_0001);
+        assert_eq!(tag.get_u8(2).unwrap(), 0b1000_0010);
+
+        let res = tag.get_bool_array(8, 16).unwrap();
+        assert_eq!(res, bits);
+
+        let res = tag.get_bool_array(u32::MAX, 2);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+        let res = tag.set_bool_array(u32::MAX, &[true, true]);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+    }
+
+    #[test]
+    fn test_id() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        assert!(tag.id() > 0);
+        let size = unsafe { ffi::plc_tag_get_size(tag.id()) };
+        assert_eq!(size as u32, tag.size().unwrap());
+    }
+
+    #[cfg(feature = "value")]
+    #[test]
+    fn test_iter_values() {
+        let tag = RawTag::new(
+            "make=system&family=library&name=debug&debug=4&elem_count=4",
+            100,
+        )
+        .unwrap();
+        let stride = tag.element_stride().unwrap();
+        let values = tag.iter_values::<u8>().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(values.len(), tag.elem_count().unwrap() as usize);
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(*v, tag.get_u8(i as u32 * stride).unwrap());
+        }
+
+        // elements larger than the stride
+        assert!(stride < 8);
+        let mut iter = tag.iter_values::<u64>();
+        let res = iter.next().unwrap();
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        assert!(iter.next().is_none());
+
+        // offset overflows
+        let mut iter = ValueIter::<u8> {
+            tag: &tag,
+            index: 2,
+            count: 4,
+            stride: u32::MAX,
+            error: None,
+            _marker: core::marker::PhantomData,
+        };
+        let res = iter.next().unwrap();
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing() {
+        use std::sync::{
+            atomic::{AtomicU64, AtomicUsize, Ordering},
+            Arc,
+        };
+        use tracing::{span, Event, Metadata, Subscriber};
+
+        /// counts `plctag.read` spans
+        #[derive(Default)]
+        struct SpanCounter {
+            next_id: AtomicU64,
+            reads: Arc<AtomicUsize>,
+        }
+
+        impl Subscriber for SpanCounter {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+                if span.metadata().name() == "plctag.read" {
+                    self.reads.fetch_add(1, Ordering::SeqCst);
+                }
+                span::Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let subscriber = SpanCounter::default();
+        let reads = subscriber.reads.clone();
+        tracing::subscriber::with_default(subscriber, || {
+            let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+            assert!(tag.read(100).is_ok());
+        });
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_begin_read() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let mut op = tag.begin_read();
+        let started = Instant::now();
+        let res = loop {
+            match op.poll() {
+                core::task::Poll::Ready(res) => break res,
+                core::task::Poll::Pending => {
+                    assert!(started.elapsed() < Duration::from_secs(1));
+                    thread::sleep(Duration::from_millis(1));
+                }
+            }
+        };
+        assert!(res.is_ok());
+        // polling again after completion
+        assert!(matches!(op.poll(), core::task::Poll::Ready(Ok(()))));
+        assert!(op.abort().is_ok());
+        assert!(!tag.status().is_pending());
+    }
+
+    #[test]
+    fn test_global_attr() {
+        let level = get_global_attr("debug", -1).unwrap();
+        assert!((0..=5).contains(&level));
+        assert!(set_global_attr("debug", level).is_ok());
+
+        let (major, _, _) = library_version();
+        assert_eq!(get_global_attr("version_major", -1).unwrap(), major);
+        let res = get_global_attr("bad\0attr", 0);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    #[test]
+    fn test_size_cache() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let size = tag.cache_size().unwrap();
+        assert_eq!(tag.size_cache.load(Ordering::Relaxed), size);
+
+        // resize behind the cache
+        let rc = unsafe { ffi::plc_tag_set_size(tag.id(), (size / 2) as i32) };
+        assert!(rc >= 0);
+        let mut buf = vec![0; size as usize];
+        // cached size is used, reading beyond the actual size fails
+        assert!(tag.get_bytes(0, &mut buf).is_err());
+        // so do bounds checks of arrays
+        #[cfg(feature = "value")]
+        assert!(tag.check_bounds(0, size as usize, 1).is_ok());
+
+        tag.invalidate_size_cache();
+        assert_eq!(tag.size_cache.load(Ordering::Relaxed), NO_SIZE);
+        assert_eq!(tag.get_bytes(0, &mut buf).unwrap(), (size / 2) as usize);
+        #[cfg(feature = "value")]
+        assert!(tag.check_bounds(0, size as usize, 1).is_err());
+    }
+
+    #[cfg(feature = "f16")]
+    #[test]
+    fn test_f16() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let value = half::f16::from_f32(1.5);
+        tag.set_f16(0, value).unwrap();
+        assert_eq!(tag.get_u16(0).unwrap(), 0x3E00);
+        assert_eq!(tag.get_f16(0).unwrap(), value);
+    }
+
+    #[test]
+    fn test_read_bytes() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let size = tag.size().unwrap() as usize;
+        let mut buf = vec![0; size + 4];
+        let n = tag.read_bytes(100, &mut buf).unwrap();
+        assert_eq!(n, size);
+
+        assert!(tag.read(100).is_ok());
+        let mut expected = vec![0; size];
+        tag.get_bytes(0, &mut expected).unwrap();
+        assert_eq!(&buf[..n], &expected[..]);
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        assert!(tag.read(100).is_ok());
+        tag.set_u8(1, 0x5A).unwrap();
+        let buf = tag.to_vec().unwrap();
+        assert_eq!(buf.len(), tag.size().unwrap() as usize);
+        assert_eq!(buf[1], 0x5A);
+    }
+
+    #[test]
+    fn test_diff_writer() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let mut writer = tag.diff_writer();
+        writer.set_u16(2, 0x1234).unwrap();
+        assert!(writer.commit(100).unwrap());
+        assert_eq!(writer.dirty_range().unwrap(), None);
+
+        // same data, no write
+        writer.set_u16(2, 0x1234).unwrap();
+        assert!(!writer.commit(100).unwrap());
+
+        writer.set_u16(4, 0x5678).unwrap();
+        assert_eq!(writer.dirty_range().unwrap(), Some(4..6));
+        assert!(writer.commit(100).unwrap());
+    }
+
+    #[test]
+    fn test_diff_range() {
+        assert_eq!(diff_range(None, &[1, 2]), Some(0..2));
+        assert_eq!(diff_range(Some(&[1, 2, 3][..]), &[1, 2, 3]), None);
+        assert_eq!(
+            diff_range(Some(&[1, 2, 3, 4][..]), &[1, 5, 6, 4]),
+            Some(1..3)
+        );
+        assert_eq!(diff_range(Some(&[1][..]), &[1, 2]), Some(0..2));
+    }
+
+    #[test]
+    fn test_string_array() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        // counted string: 1 byte count word, 8 bytes of characters
+        let attrs = tag.string_attrs();
+        attrs.set_counted(true).unwrap();
+        attrs.set_count_word_bytes(1).unwrap();
+        attrs.set_max_capacity(8).unwrap();
+        attrs.set_total_length(9).unwrap();
+
+        for (i, s) in ["a", "bc", "def"].iter().enumerate() {
+            tag.try_set_string(i as u32 * 9, *s).unwrap();
+        }
+        let res = tag.get_string_array(0, 3).unwrap();
+        assert_eq!(res, vec!["a", "bc", "def"]);
+        assert!(tag.get_string_array(0, 0).unwrap().is_empty());
+    }
 }
@@ -22,7 +22,18 @@ pub enum Status {
     Err(i32),
 }
 
+impl Default for Status {
+    /// [`Status::Ok`]
+    #[inline(always)]
+    fn default() -> Self {
+        Status::Ok
+    }
+}
+
 impl Status {
+    /// same as [`Status::Ok`]
+    pub const OK: Status = Status::Ok;
+
     /// create [`Status`] from return code of `libplctag` functions
     #[inline(always)]
     pub fn new(rc: i32) -> Self {
@@ -60,6 +71,41 @@ impl Status {
         }
     }
 
+    /// is transient error? the operation might succeed if retried.
+    ///
+    /// transient errors:
+    /// `PLCTAG_ERR_ABORT`, `PLCTAG_ERR_BAD_CONNECTION`, `PLCTAG_ERR_BAD_GATEWAY`, `PLCTAG_ERR_BUSY`,
+    /// `PLCTAG_ERR_NO_DATA`, `PLCTAG_ERR_NO_RESOURCES`, `PLCTAG_ERR_PARTIAL`, `PLCTAG_ERR_READ`,
+    /// `PLCTAG_ERR_TIMEOUT`, `PLCTAG_ERR_WINSOCK`, `PLCTAG_ERR_WRITE`
+    #[inline]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Status::Err(rc) => matches!(
+                *rc,
+                ffi::PLCTAG_ERR_ABORT
+                    | ffi::PLCTAG_ERR_BAD_CONNECTION
+                    | ffi::PLCTAG_ERR_BAD_GATEWAY
+                    | ffi::PLCTAG_ERR_BUSY
+                    | ffi::PLCTAG_ERR_NO_DATA
+                    | ffi::PLCTAG_ERR_NO_RESOURCES
+                    | ffi::PLCTAG_ERR_PARTIAL
+                    | ffi::PLCTAG_ERR_READ
+                    | ffi::PLCTAG_ERR_TIMEOUT
+                    | ffi::PLCTAG_ERR_WINSOCK
+                    | ffi::PLCTAG_ERR_WRITE
+            ),
+            _ => false,
+        }
+    }
+
+    /// is permanent error? retry will not help, i.e. `PLCTAG_ERR_BAD_PARAM`, `PLCTAG_ERR_OUT_OF_BOUNDS`.
+    ///
+    /// all errors other than transient ones, see [`Status::is_transient`]
+    #[inline]
+    pub fn is_permanent(&self) -> bool {
+        self.is_err() && !self.is_transient()
+    }
+
     /// into [`Result`]
     #[inline(always)]
     pub fn into_result(self) -> Result<()> {
@@ -70,7 +116,28 @@ impl Status {
         }
     }
 
-    /// decode status from error code to String
+    /// raw status code of `libplctag`
+    #[inline(always)]
+    pub fn code(&self) -> i32 {
+        (*self).into()
+    }
+
+    /// typed kind of the status for matching common codes, see [`StatusKind`]
+    #[inline]
+    pub fn kind(&self) -> StatusKind {
+        match *self {
+            Status::Ok => StatusKind::Ok,
+            Status::Pending => StatusKind::Pending,
+            Status::Err(ffi::PLCTAG_ERR_TIMEOUT) => StatusKind::Timeout,
+            Status::Err(ffi::PLCTAG_ERR_BAD_CONNECTION) => StatusKind::NoConnection,
+            Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS) => StatusKind::OutOfBounds,
+            Status::Err(ffi::PLCTAG_ERR_BAD_PARAM) => StatusKind::BadParam,
+            Status::Err(ffi::PLCTAG_ERR_NOT_FOUND) => StatusKind::NotFound,
+            Status::Err(rc) => StatusKind::Other(rc),
+        }
+    }
+
+    /// decode status from error code to human-readable string, i.e. `PLCTAG_ERR_TIMEOUT`
     ///
     /// see `libplctag` for all status code
     ///
@@ -83,13 +150,14 @@ impl Status {
     /// assert_eq!(msg, "PLCTAG_STATUS_OK");
     /// ```
     #[inline]
-    pub fn decode(&self) -> String {
-        let rc = (*self).into();
+    pub fn decode(&self) -> &'static str {
+        let rc = self.code();
 
+        // messages of `libplctag` are static ASCII strings
         unsafe {
             let ptr = ffi::plc_tag_decode_error(rc);
-            let msg = CStr::from_ptr(ptr);
-            msg.to_string_lossy().to_string()
+            let msg: &'static CStr = CStr::from_ptr(ptr);
+            msg.to_str().unwrap_or("PLCTAG_ERR_UNKNOWN")
         }
     }
 }
@@ -121,8 +189,66 @@ impl fmt::Display for Status {
 
 impl fmt::Debug for Status {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let rc: i32 = (*self).into();
-        write!(f, "STATUS {}: {}", &rc, self.decode())
+        write!(f, "STATUS {}: {}", self.code(), self.decode())
+    }
+}
+
+/// common status codes of `libplctag`, see [`Status::kind`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StatusKind {
+    /// `PLCTAG_STATUS_OK`
+    Ok,
+    /// `PLCTAG_STATUS_PENDING`
+    Pending,
+    /// `PLCTAG_ERR_TIMEOUT`
+    Timeout,
+    /// `PLCTAG_ERR_BAD_CONNECTION`
+    NoConnection,
+    /// `PLCTAG_ERR_OUT_OF_BOUNDS`
+    OutOfBounds,
+    /// `PLCTAG_ERR_BAD_PARAM`
+    BadParam,
+    /// `PLCTAG_ERR_NOT_FOUND`
+    NotFound,
+    /// other error codes
+    Other(i32),
+}
+
+/// connection state guessed from tag status, see [`RawTag::connection_status`](crate::RawTag::connection_status)
+///
+/// # Note
+/// `libplctag` does not expose the state of the underlying connection, so this is a heuristic:
+/// - `PLCTAG_STATUS_OK` is treated as connected
+/// - `PLCTAG_STATUS_PENDING` is treated as connecting
+/// - connection level errors (`PLCTAG_ERR_BAD_CONNECTION`, `PLCTAG_ERR_BAD_GATEWAY`, `PLCTAG_ERR_BAD_DEVICE`,
+///   `PLCTAG_ERR_OPEN`, `PLCTAG_ERR_CLOSE`, `PLCTAG_ERR_WINSOCK`) are treated as disconnected
+/// - other errors are tag level errors, the connection is treated as connected
+#[derive(Debug, Copy, Clone)]
+pub enum ConnectionStatus {
+    /// connected
+    Connected,
+    /// connecting, or an operation is in flight
+    Connecting,
+    /// disconnected with the error
+    Disconnected(Status),
+}
+
+impl From<Status> for ConnectionStatus {
+    #[inline]
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Ok => ConnectionStatus::Connected,
+            Status::Pending => ConnectionStatus::Connecting,
+            Status::Err(
+                ffi::PLCTAG_ERR_BAD_CONNECTION
+                | ffi::PLCTAG_ERR_BAD_GATEWAY
+                | ffi::PLCTAG_ERR_BAD_DEVICE
+                | ffi::PLCTAG_ERR_OPEN
+                | ffi::PLCTAG_ERR_CLOSE
+                | ffi::PLCTAG_ERR_WINSOCK,
+            ) => ConnectionStatus::Disconnected(status),
+            Status::Err(_) => ConnectionStatus::Connected,
+        }
     }
 }
 
@@ -143,4 +269,80 @@ mod tests {
         let msg = status.decode();
         assert_eq!(msg, "PLCTAG_STATUS_PENDING");
     }
+
+    #[test]
+    fn test_status_category() {
+        let status = Status::Err(ffi::PLCTAG_ERR_TIMEOUT);
+        assert!(status.is_timeout());
+        assert!(status.is_transient());
+        assert!(!status.is_permanent());
+
+        let status = Status::Err(ffi::PLCTAG_ERR_NO_DATA);
+        assert!(status.is_transient());
+        assert!(!status.is_permanent());
+
+        let status = Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+        assert!(!status.is_transient());
+        assert!(status.is_permanent());
+
+        assert!(!Status::Ok.is_transient());
+        assert!(!Status::Ok.is_permanent());
+        assert!(!Status::Pending.is_permanent());
+    }
+
+    #[test]
+    fn test_status_decode() {
+        let status = Status::Err(ffi::PLCTAG_ERR_TIMEOUT);
+        assert_eq!(status.code(), ffi::PLCTAG_ERR_TIMEOUT);
+        assert!(status.decode().contains("TIMEOUT"));
+        assert!(status.to_string().contains("TIMEOUT"));
+    }
+
+    #[test]
+    fn test_connection_status() {
+        let status = ConnectionStatus::from(Status::Err(ffi::PLCTAG_ERR_BAD_CONNECTION));
+        assert!(
+            matches!(status, ConnectionStatus::Disconnected(e) if e.code() == ffi::PLCTAG_ERR_BAD_CONNECTION)
+        );
+        let status = ConnectionStatus::from(Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS));
+        assert!(matches!(status, ConnectionStatus::Connected));
+        let status = ConnectionStatus::from(Status::Pending);
+        assert!(matches!(status, ConnectionStatus::Connecting));
+    }
+
+    #[test]
+    fn test_status_default() {
+        assert!(Status::default().is_ok());
+        assert!(Status::OK.is_ok());
+    }
+
+    #[test]
+    fn test_status_kind() {
+        assert_eq!(Status::Ok.kind(), StatusKind::Ok);
+        assert_eq!(Status::Pending.kind(), StatusKind::Pending);
+        assert_eq!(
+            Status::Err(ffi::PLCTAG_ERR_TIMEOUT).kind(),
+            StatusKind::Timeout
+        );
+        assert_eq!(
+            Status::Err(ffi::PLCTAG_ERR_BAD_CONNECTION).kind(),
+            StatusKind::NoConnection
+        );
+        assert_eq!(
+            Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS).kind(),
+            StatusKind::OutOfBounds
+        );
+        assert_eq!(
+            Status::Err(ffi::PLCTAG_ERR_BAD_PARAM).kind(),
+            StatusKind::BadParam
+        );
+        assert_eq!(
+            Status::Err(ffi::PLCTAG_ERR_NOT_FOUND).kind(),
+            StatusKind::NotFound
+        );
+        assert_eq!(
+            Status::Err(ffi::PLCTAG_ERR_BUSY).kind(),
+            StatusKind::Other(ffi::PLCTAG_ERR_BUSY)
+        );
+    }
 }
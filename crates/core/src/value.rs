@@ -4,15 +4,17 @@
 // Copyright: 2022, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use crate::{RawTag, Result};
-use alloc::{borrow::Cow, rc::Rc, sync::Arc};
-use core::marker::PhantomData;
+use crate::{ffi, raw::element_offset, RawTag, Result, Status};
+use alloc::{borrow::Cow, rc::Rc, string::String, sync::Arc};
+use core::{marker::PhantomData, time::Duration};
 use paste::paste;
 
 macro_rules! value_impl {
     ($type: ident) => {
         paste! {
             impl Decode for $type {
+                const SIZE: u32 = core::mem::size_of::<$type>() as u32;
+
                 #[inline]
                 fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
                     let v = tag.[<get_ $type>](offset)?;
@@ -21,6 +23,8 @@ macro_rules! value_impl {
 
             }
             impl Encode for $type {
+                const SIZE: u32 = core::mem::size_of::<$type>() as u32;
+
                 #[inline]
                 fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
                     tag.[<set_ $type>](offset, *self)
@@ -30,6 +34,82 @@ macro_rules! value_impl {
     };
 }
 
+/// `offset + size`, `PLCTAG_ERR_OUT_OF_BOUNDS` if overflows
+#[inline(always)]
+fn next_offset(offset: u32, size: u32) -> Result<u32> {
+    offset
+        .checked_add(size)
+        .ok_or(Status::Err(ffi::PLCTAG_ERR_OUT_OF_BOUNDS))
+}
+
+/// `PLCTAG_ERR_BAD_PARAM` if `size` is 0, such elements would overlap
+#[inline(always)]
+fn check_elem_size(size: u32) -> Result<()> {
+    if size == 0 {
+        return Err(Status::Err(ffi::PLCTAG_ERR_BAD_PARAM));
+    }
+    Ok(())
+}
+
+/// tuples are packed sequentially without padding,
+/// each element is at the offset of previous one plus its `SIZE`.
+/// tuples of elements of `SIZE` 0 return `PLCTAG_ERR_BAD_PARAM`.
+macro_rules! tuple_impl {
+    ($($T: ident $v: ident),+) => {
+        impl<$($T: Decode),+> Decode for ($($T,)+) {
+            const SIZE: u32 = 0 $(+ $T::SIZE)+;
+
+            #[inline]
+            #[allow(unused_assignments)]
+            fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
+                $(check_elem_size($T::SIZE)?;)+
+                let mut offset = offset;
+                let mut size = 0;
+                $(
+                    offset = next_offset(offset, size)?;
+                    let $v = $T::decode(tag, offset)?;
+                    size = $T::SIZE;
+                )+
+                Ok(($($v,)+))
+            }
+
+            #[inline]
+            #[allow(unused_assignments)]
+            fn decode_in_place(tag: &RawTag, offset: u32, place: &mut Self) -> Result<()> {
+                $(check_elem_size($T::SIZE)?;)+
+                let mut offset = offset;
+                let mut size = 0;
+                let ($($v,)+) = place;
+                $(
+                    offset = next_offset(offset, size)?;
+                    $T::decode_in_place(tag, offset, $v)?;
+                    size = $T::SIZE;
+                )+
+                Ok(())
+            }
+        }
+
+        impl<$($T: Encode),+> Encode for ($($T,)+) {
+            const SIZE: u32 = 0 $(+ $T::SIZE)+;
+
+            #[inline]
+            #[allow(unused_assignments)]
+            fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
+                $(check_elem_size($T::SIZE)?;)+
+                let mut offset = offset;
+                let mut size = 0;
+                let ($($v,)+) = self;
+                $(
+                    offset = next_offset(offset, size)?;
+                    $v.encode(tag, offset)?;
+                    size = $T::SIZE;
+                )+
+                Ok(())
+            }
+        }
+    };
+}
+
 /// this trait abstracts tag value.
 /// you can use the trait to implement your UDT.
 ///
@@ -68,6 +148,8 @@ macro_rules! value_impl {
 ///     v2:u16,
 /// }
 /// impl Decode for MyUDT {
+///     const SIZE: u32 = 4;
+///
 ///     fn decode(tag: &RawTag, offset: u32) -> Result<Self>{
 ///         let v1 = u16::decode(tag, offset)?;
 ///         let v2 = u16::decode(tag, offset + 2)?;
@@ -75,6 +157,8 @@ macro_rules! value_impl {
 ///     }
 /// }
 /// impl Encode for MyUDT {
+///     const SIZE: u32 = 4;
+///
 ///     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()>{
 ///         self.v1.encode(tag, offset)?;
 ///         self.v2.encode(tag, offset+2)?;
@@ -107,6 +191,13 @@ macro_rules! value_impl {
 /// Do not perform expensive operations when you derives [`Decode`] or [`Encode`].
 
 pub trait Decode: Sized {
+    /// size in bytes of the value in the tag buffer, used to compute element offsets of arrays.
+    ///
+    /// it must match the layout in PLC, which often differs from `size_of::<Self>()`.
+    /// defaults to 0, variable or unknown size; arrays and tuples of such values
+    /// return `PLCTAG_ERR_BAD_PARAM`.
+    const SIZE: u32 = 0;
+
     /// get value at specified byte offset
     fn decode(tag: &RawTag, offset: u32) -> Result<Self>;
 
@@ -120,6 +211,16 @@ pub trait Decode: Sized {
 
 /// see [`Decode`]
 pub trait Encode {
+    /// size in bytes of the value in the tag buffer, used to compute element offsets of arrays.
+    ///
+    /// it must match the layout in PLC, which often differs from `size_of::<Self>()`.
+    /// defaults to 0, variable or unknown size; arrays and tuples of such values
+    /// return `PLCTAG_ERR_BAD_PARAM`.
+    ///
+    /// # Breaking change
+    /// since `plctag-core` 0.5, `Encode` is no longer object safe, `dyn Encode` is not supported.
+    const SIZE: u32 = 0;
+
     /// set value at specified byte offset
     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()>;
 }
@@ -136,7 +237,124 @@ value_impl!(u64);
 value_impl!(f32);
 value_impl!(f64);
 
+tuple_impl!(A a);
+tuple_impl!(A a, B b);
+tuple_impl!(A a, B b, C c);
+tuple_impl!(A a, B b, C c, D d);
+tuple_impl!(A a, B b, C c, D d, E e);
+tuple_impl!(A a, B b, C c, D d, E e, F f);
+
+/// strings are read and written by `libplctag` string functions,
+/// which rely on the string layout attributes of the tag, see [`RawTag::string_attrs`].
+///
+/// `SIZE` is 0 because the space occupied depends on the string layout;
+/// tuples or arrays of strings return `PLCTAG_ERR_BAD_PARAM`, use explicit offsets instead.
+impl Decode for String {
+    const SIZE: u32 = 0;
+
+    #[inline]
+    fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
+        tag.get_string_owned(offset)
+    }
+}
+
+/// see [`Decode`] for `String`
+impl Encode for String {
+    const SIZE: u32 = 0;
+
+    #[inline]
+    fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
+        tag.try_set_string(offset, self.as_str())
+    }
+}
+
+/// see [`Decode`] for `String`, strings with interior NUL return `PLCTAG_ERR_BAD_PARAM`
+impl Encode for &str {
+    const SIZE: u32 = 0;
+
+    #[inline]
+    fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
+        tag.try_set_string(offset, *self)
+    }
+}
+
+/// characters are stored as single byte, i.e. a SINT holding ASCII.
+///
+/// decoding maps bytes >= 0x80 to `U+0080..=U+00FF`; encoding a non-ASCII char returns `PLCTAG_ERR_ENCODE`.
+impl Decode for char {
+    const SIZE: u32 = 1;
+
+    #[inline]
+    fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
+        let v = tag.get_u8(offset)?;
+        Ok(char::from(v))
+    }
+}
+
+/// see [`Decode`] for `char`
+impl Encode for char {
+    const SIZE: u32 = 1;
+
+    #[inline]
+    fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
+        if !self.is_ascii() {
+            return Err(Status::Err(ffi::PLCTAG_ERR_ENCODE));
+        }
+        tag.set_u8(offset, *self as u8)
+    }
+}
+
+/// durations are stored as `u32` milliseconds, i.e. a DINT timer preset or accumulator.
+///
+/// encoding a duration longer than `u32::MAX` milliseconds returns `PLCTAG_ERR_TOO_LARGE`;
+/// sub-millisecond parts are truncated.
+impl Decode for Duration {
+    const SIZE: u32 = 4;
+
+    #[inline]
+    fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
+        let ms = tag.get_u32(offset)?;
+        Ok(Duration::from_millis(ms as u64))
+    }
+}
+
+/// see [`Decode`] for `Duration`
+impl Encode for Duration {
+    const SIZE: u32 = 4;
+
+    #[inline]
+    fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
+        let ms =
+            u32::try_from(self.as_millis()).map_err(|_| Status::Err(ffi::PLCTAG_ERR_TOO_LARGE))?;
+        tag.set_u32(offset, ms)
+    }
+}
+
+/// half-precision floats are stored as `u16`, see [`RawTag::get_f16`]
+#[cfg(feature = "f16")]
+impl Decode for half::f16 {
+    const SIZE: u32 = 2;
+
+    #[inline]
+    fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
+        tag.get_f16(offset)
+    }
+}
+
+/// see [`Decode`] for `half::f16`
+#[cfg(feature = "f16")]
+impl Encode for half::f16 {
+    const SIZE: u32 = 2;
+
+    #[inline]
+    fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
+        tag.set_f16(offset, *self)
+    }
+}
+
 impl<T: Decode> Decode for Option<T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
         let v = T::decode(tag, offset)?;
@@ -158,6 +376,8 @@ impl<T: Decode> Decode for Option<T> {
 }
 
 impl<T: Encode> Encode for Option<T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
         if let Some(ref v) = self {
@@ -168,6 +388,8 @@ impl<T: Encode> Encode for Option<T> {
 }
 
 impl<T: Encode> Encode for &T {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
         T::encode(self, tag, offset)
@@ -175,6 +397,8 @@ impl<T: Encode> Encode for &T {
 }
 
 impl<T: Decode + Clone> Decode for Cow<'_, T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
         let v = T::decode(tag, offset)?;
@@ -188,6 +412,8 @@ impl<T: Decode + Clone> Decode for Cow<'_, T> {
 }
 
 impl<T: Encode + Clone> Encode for Cow<'_, T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
         T::encode(self, tag, offset)
@@ -195,6 +421,8 @@ impl<T: Encode + Clone> Encode for Cow<'_, T> {
 }
 
 impl<T: Encode> Encode for Arc<T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
         T::encode(self, tag, offset)
@@ -202,6 +430,8 @@ impl<T: Encode> Encode for Arc<T> {
 }
 
 impl<T: Decode> Decode for Arc<T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
         let v = T::decode(tag, offset)?;
@@ -219,6 +449,8 @@ impl<T: Decode> Decode for Arc<T> {
 }
 
 impl<T: Encode> Encode for Rc<T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
         T::encode(self, tag, offset)
@@ -226,6 +458,8 @@ impl<T: Encode> Encode for Rc<T> {
 }
 
 impl<T: Decode> Decode for Rc<T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
         let v = T::decode(tag, offset)?;
@@ -243,6 +477,8 @@ impl<T: Decode> Decode for Rc<T> {
 }
 
 impl<T> Encode for PhantomData<T> {
+    const SIZE: u32 = 0;
+
     #[inline]
     fn encode(&self, _tag: &RawTag, _offset: u32) -> Result<()> {
         Ok(())
@@ -250,6 +486,8 @@ impl<T> Encode for PhantomData<T> {
 }
 
 impl<T> Decode for PhantomData<T> {
+    const SIZE: u32 = 0;
+
     #[inline]
     fn decode(_tag: &RawTag, _offset: u32) -> Result<Self> {
         Ok(Default::default())
@@ -257,6 +495,8 @@ impl<T> Decode for PhantomData<T> {
 }
 
 impl<T: Encode> Encode for Box<T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
         T::encode(self, tag, offset)
@@ -264,6 +504,8 @@ impl<T: Encode> Encode for Box<T> {
 }
 
 impl<T: Decode> Decode for Box<T> {
+    const SIZE: u32 = T::SIZE;
+
     #[inline]
     fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
         let v = T::decode(tag, offset)?;
@@ -277,6 +519,8 @@ impl<T: Decode> Decode for Box<T> {
 }
 
 impl Encode for &[u8] {
+    // length only known at runtime
+    const SIZE: u32 = 0;
     #[inline]
     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
         let _ = tag.set_bytes(offset, self)?;
@@ -284,6 +528,174 @@ impl Encode for &[u8] {
     }
 }
 
+/// `SIZE` of `n` elements, fails to compile if it overflows `u32`
+const fn array_size(size: u32, n: usize) -> u32 {
+    if n > u32::MAX as usize {
+        panic!("array SIZE overflows u32");
+    }
+    match size.checked_mul(n as u32) {
+        Some(v) => v,
+        None => panic!("array SIZE overflows u32"),
+    }
+}
+
+/// elements are decoded one by one at `offset + i * T::SIZE`,
+/// elements of `SIZE` 0 return `PLCTAG_ERR_BAD_PARAM`.
+///
+/// Note: `[bool; N]` uses byte-per-bool semantics like [`RawTag::get_bool`], not bit packing.
+impl<T: Decode, const N: usize> Decode for [T; N] {
+    const SIZE: u32 = array_size(T::SIZE, N);
+
+    #[inline]
+    fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
+        check_elem_size(T::SIZE)?;
+        let mut res = Ok(());
+        let items: [Option<T>; N] = core::array::from_fn(|i| {
+            if res.is_err() {
+                return None;
+            }
+            match element_offset(offset, i, T::SIZE).and_then(|offset| T::decode(tag, offset)) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    res = Err(e);
+                    None
+                }
+            }
+        });
+        res?;
+        Ok(items.map(|v| v.unwrap()))
+    }
+
+    #[inline]
+    fn decode_in_place(tag: &RawTag, offset: u32, place: &mut Self) -> Result<()> {
+        check_elem_size(T::SIZE)?;
+        for (i, v) in place.iter_mut().enumerate() {
+            T::decode_in_place(tag, element_offset(offset, i, T::SIZE)?, v)?;
+        }
+        Ok(())
+    }
+}
+
+/// see [`Decode`] for `[T; N]`
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    const SIZE: u32 = array_size(T::SIZE, N);
+
+    #[inline]
+    fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
+        check_elem_size(T::SIZE)?;
+        for (i, v) in self.iter().enumerate() {
+            v.encode(tag, element_offset(offset, i, T::SIZE)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// big-endian value, bytes are read/written in big-endian order regardless of the tag byte order.
+///
+/// # Examples
+/// ```rust,no_run
+/// use plctag_core::Be;
+///
+/// #[derive(Debug, Default)]
+/// struct MyUDT {
+///     swapped: Be<u32>,
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Be<T>(pub T);
+
+/// little-endian value, bytes are read/written in little-endian order regardless of the tag byte order.
+///
+/// see [`Be`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Le<T>(pub T);
+
+macro_rules! endian_impl {
+    ($type: ident) => {
+        endian_impl!(Be, $type, from_be_bytes, to_be_bytes);
+        endian_impl!(Le, $type, from_le_bytes, to_le_bytes);
+    };
+    ($wrapper: ident, $type: ident, $from: ident, $to: ident) => {
+        impl Decode for $wrapper<$type> {
+            const SIZE: u32 = core::mem::size_of::<$type>() as u32;
+
+            #[inline]
+            fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
+                let mut buf = [0_u8; core::mem::size_of::<$type>()];
+                tag.get_bytes_unchecked(offset, &mut buf)?;
+                Ok($wrapper($type::$from(buf)))
+            }
+        }
+
+        impl Encode for $wrapper<$type> {
+            const SIZE: u32 = core::mem::size_of::<$type>() as u32;
+
+            #[inline]
+            fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
+                tag.set_bytes_unchecked(offset, &self.0.$to())?;
+                Ok(())
+            }
+        }
+    };
+}
+
+endian_impl!(i16);
+endian_impl!(u16);
+endian_impl!(i32);
+endian_impl!(u32);
+endian_impl!(i64);
+endian_impl!(u64);
+endian_impl!(f32);
+endian_impl!(f64);
+
+/// fixed-size raw bytes, read/written in one call instead of byte by byte like `[u8; N]`.
+/// `SIZE` is `N`, it fails to compile if `N` overflows `u32`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use plctag_core::{Bytes, RawTag, ValueExt};
+///
+/// let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+/// let value: Bytes<16> = tag.get_value(0).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Default for Bytes<N> {
+    #[inline]
+    fn default() -> Self {
+        Bytes([0; N])
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Bytes<N> {
+    #[inline]
+    fn from(v: [u8; N]) -> Self {
+        Bytes(v)
+    }
+}
+
+impl<const N: usize> Decode for Bytes<N> {
+    const SIZE: u32 = array_size(1, N);
+
+    #[inline]
+    fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
+        let mut buf = [0_u8; N];
+        tag.get_bytes_unchecked(offset, &mut buf)?;
+        Ok(Bytes(buf))
+    }
+}
+
+impl<const N: usize> Encode for Bytes<N> {
+    const SIZE: u32 = array_size(1, N);
+
+    #[inline]
+    fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
+        tag.set_bytes_unchecked(offset, &self.0)?;
+        Ok(())
+    }
+}
+
 /// generic value getter/setter
 pub trait ValueExt {
     /// get tag value of `T` that derives [`Decode`]
@@ -298,6 +710,39 @@ pub trait ValueExt {
 
     /// set tag value that derives [`Encode`]
     fn set_value<T: Encode>(&self, byte_offset: u32, value: T) -> Result<()>;
+
+    /// copy raw bytes from byte offset into `buf`, clamped to the tag size.
+    /// returns the number of bytes actually copied
+    ///
+    /// the default implementation copies byte by byte until `PLCTAG_ERR_OUT_OF_BOUNDS`
+    fn get_bytes_checked(&self, byte_offset: u32, buf: &mut [u8]) -> Result<usize> {
+        for (i, v) in buf.iter_mut().enumerate() {
+            let res = element_offset(byte_offset, i, 1).and_then(|offset| self.get_value(offset));
+            match res {
+                Ok(byte) => *v = byte,
+                Err(e) if e.code() == ffi::PLCTAG_ERR_OUT_OF_BOUNDS => return Ok(i),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(buf.len())
+    }
+
+    /// copy raw bytes of `buf` to byte offset, clamped to the tag size.
+    /// returns the number of bytes actually copied
+    ///
+    /// the default implementation copies byte by byte until `PLCTAG_ERR_OUT_OF_BOUNDS`
+    fn set_bytes_checked(&self, byte_offset: u32, buf: &[u8]) -> Result<usize> {
+        for (i, v) in buf.iter().enumerate() {
+            let res =
+                element_offset(byte_offset, i, 1).and_then(|offset| self.set_value(offset, *v));
+            match res {
+                Ok(()) => {}
+                Err(e) if e.code() == ffi::PLCTAG_ERR_OUT_OF_BOUNDS => return Ok(i),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(buf.len())
+    }
 }
 
 impl ValueExt for RawTag {
@@ -315,6 +760,16 @@ impl ValueExt for RawTag {
     fn set_value<T: Encode>(&self, byte_offset: u32, value: T) -> Result<()> {
         value.encode(self, byte_offset)
     }
+
+    #[inline]
+    fn get_bytes_checked(&self, byte_offset: u32, buf: &mut [u8]) -> Result<usize> {
+        self.get_bytes(byte_offset, buf)
+    }
+
+    #[inline]
+    fn set_bytes_checked(&self, byte_offset: u32, buf: &[u8]) -> Result<usize> {
+        self.set_bytes(byte_offset, buf)
+    }
 }
 
 impl<Tag: ValueExt> ValueExt for &Tag {
@@ -327,6 +782,16 @@ impl<Tag: ValueExt> ValueExt for &Tag {
     fn set_value<T: Encode>(&self, byte_offset: u32, value: T) -> Result<()> {
         Tag::set_value(self, byte_offset, value)
     }
+
+    #[inline]
+    fn get_bytes_checked(&self, byte_offset: u32, buf: &mut [u8]) -> Result<usize> {
+        Tag::get_bytes_checked(self, byte_offset, buf)
+    }
+
+    #[inline]
+    fn set_bytes_checked(&self, byte_offset: u32, buf: &[u8]) -> Result<usize> {
+        Tag::set_bytes_checked(self, byte_offset, buf)
+    }
 }
 
 impl<Tag: ValueExt> ValueExt for Box<Tag> {
@@ -338,4 +803,308 @@ impl<Tag: ValueExt> ValueExt for Box<Tag> {
     fn set_value<T: Encode>(&self, byte_offset: u32, value: T) -> Result<()> {
         (**self).set_value(byte_offset, value)
     }
+    #[inline]
+    fn get_bytes_checked(&self, byte_offset: u32, buf: &mut [u8]) -> Result<usize> {
+        (**self).get_bytes_checked(byte_offset, buf)
+    }
+    #[inline]
+    fn set_bytes_checked(&self, byte_offset: u32, buf: &[u8]) -> Result<usize> {
+        (**self).set_bytes_checked(byte_offset, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// value that takes a quarter of the address space, it does not touch the tag
+    #[derive(Debug, PartialEq)]
+    struct Huge;
+
+    impl Decode for Huge {
+        const SIZE: u32 = 1 << 30;
+
+        fn decode(_tag: &RawTag, _offset: u32) -> Result<Self> {
+            Ok(Huge)
+        }
+    }
+
+    impl Encode for Huge {
+        const SIZE: u32 = 1 << 30;
+
+        fn encode(&self, _tag: &RawTag, _offset: u32) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_array() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        assert_eq!(<[u16; 3] as Decode>::SIZE, 6);
+
+        tag.set_value(0, [1_u16, 2, 3]).unwrap();
+        let arr: [u16; 3] = tag.get_value(0).unwrap();
+        assert_eq!(arr, [1, 2, 3]);
+        assert_eq!(tag.get_u16(4).unwrap(), 3);
+
+        let mut arr = [0_u16; 3];
+        tag.get_value_in_place(0, &mut arr).unwrap();
+        assert_eq!(arr, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_array_offset_overflow() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let res: Result<[Huge; 2]> = tag.get_value(0);
+        assert!(res.is_ok());
+
+        // the second element is at 4GiB
+        let base = 3 << 30;
+        let res: Result<[Huge; 2]> = tag.get_value(base);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+        let mut place = [Huge, Huge];
+        let res = tag.get_value_in_place(base, &mut place);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+        let res = tag.set_value(base, [Huge, Huge]);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+    }
+
+    #[test]
+    fn test_tuple() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        assert_eq!(<(u16, u32, f32) as Decode>::SIZE, 10);
+
+        tag.set_value(0, (1_u16, 2_u32, 3.5_f32)).unwrap();
+        assert_eq!(tag.get_u32(2).unwrap(), 2);
+        let value: (u16, u32, f32) = tag.get_value(0).unwrap();
+        assert_eq!(value, (1, 2, 3.5));
+    }
+
+    #[test]
+    fn test_tuple_offset_overflow() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        // the last element ends at 4GiB
+        let res: Result<(Huge, Huge, Huge)> = tag.get_value(1 << 30);
+        assert!(res.is_ok());
+
+        // the second element is at 4GiB
+        let base = 3 << 30;
+        let res: Result<(Huge, Huge)> = tag.get_value(base);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+        let mut place = (Huge, Huge);
+        let res = tag.get_value_in_place(base, &mut place);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+        let res = tag.set_value(base, (Huge, Huge));
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_OUT_OF_BOUNDS);
+    }
+
+    #[test]
+    fn test_endian() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        tag.set_bytes(0, &[0x12, 0x34]).unwrap();
+        let value: Be<u16> = tag.get_value(0).unwrap();
+        assert_eq!(value, Be(0x1234));
+        let value: Le<u16> = tag.get_value(0).unwrap();
+        assert_eq!(value, Le(0x3412));
+
+        tag.set_value(0, Be(0x5678_u16)).unwrap();
+        let mut buf = [0; 2];
+        tag.get_bytes(0, &mut buf).unwrap();
+        assert_eq!(buf, [0x56, 0x78]);
+    }
+
+    #[test]
+    fn test_bytes_checked() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let size = tag.size().unwrap();
+        let tag = Box::new(tag);
+
+        let n = tag.set_bytes_checked(2, &[1, 2, 3]).unwrap();
+        assert_eq!(n, 3);
+        let mut buf = [0; 3];
+        let n = tag.get_bytes_checked(2, &mut buf).unwrap();
+        assert_eq!(n, 3);
+        assert_eq!(buf, [1, 2, 3]);
+
+        // partial window at the end of tag
+        let mut buf = [0; 8];
+        let n = tag.get_bytes_checked(size - 2, &mut buf).unwrap();
+        assert_eq!(n, 2);
+        let n = tag.set_bytes_checked(size - 1, &[1, 2]).unwrap();
+        assert_eq!(n, 1);
+        let n = tag.get_bytes_checked(size, &mut buf).unwrap();
+        assert_eq!(n, 0);
+
+        // partial window at a nonzero offset
+        tag.set_bytes_checked(size - 4, &[4, 5, 6, 7]).unwrap();
+        let mut buf = [0; 8];
+        let n = tag.get_bytes_checked(size - 3, &mut buf).unwrap();
+        assert_eq!(n, 3);
+        assert_eq!(buf, [5, 6, 7, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_bytes_checked_default() {
+        /// implements only the required methods
+        struct Wrapper(RawTag);
+
+        impl ValueExt for Wrapper {
+            fn get_value<T: Decode>(&self, byte_offset: u32) -> Result<T> {
+                self.0.get_value(byte_offset)
+            }
+
+            fn set_value<T: Encode>(&self, byte_offset: u32, value: T) -> Result<()> {
+                self.0.set_value(byte_offset, value)
+            }
+        }
+
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let size = tag.size().unwrap();
+        let tag = Wrapper(tag);
+
+        let n = tag.set_bytes_checked(2, &[1, 2, 3]).unwrap();
+        assert_eq!(n, 3);
+        assert_eq!(tag.0.get_u8(4).unwrap(), 3);
+
+        // partial window at a nonzero offset
+        tag.set_bytes_checked(size - 4, &[4, 5, 6, 7]).unwrap();
+        let mut buf = [0; 8];
+        let n = tag.get_bytes_checked(size - 3, &mut buf).unwrap();
+        assert_eq!(n, 3);
+        assert_eq!(buf, [5, 6, 7, 0, 0, 0, 0, 0]);
+        let n = tag.set_bytes_checked(size - 1, &[8, 9]).unwrap();
+        assert_eq!(n, 1);
+        assert_eq!(tag.0.get_u8(size - 1).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_string() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        // counted string: 1 byte count word, 20 bytes of characters
+        let attrs = tag.string_attrs();
+        attrs.set_counted(true).unwrap();
+        attrs.set_count_word_bytes(1).unwrap();
+        attrs.set_max_capacity(20).unwrap();
+        attrs.set_total_length(21).unwrap();
+
+        tag.set_value(0, String::from("hello")).unwrap();
+        let value: String = tag.get_value(0).unwrap();
+        assert_eq!(value, "hello");
+    }
+
+    #[test]
+    fn test_zero_sized_elements() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let res: Result<[String; 2]> = tag.get_value(0);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        let mut place = [String::new(), String::new()];
+        let res = tag.get_value_in_place(0, &mut place);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        let res = tag.set_value(0, ["a", "b"]);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+
+        let res: Result<(u8, String)> = tag.get_value(0);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        let mut place = (0_u8, String::new());
+        let res = tag.get_value_in_place(0, &mut place);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        let res = tag.set_value(0, (1_u8, "a"));
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    /// value that does not define `SIZE`
+    #[derive(Debug, Default)]
+    struct Unsized;
+
+    impl Decode for Unsized {
+        fn decode(_tag: &RawTag, _offset: u32) -> Result<Self> {
+            Ok(Unsized)
+        }
+    }
+
+    impl Encode for Unsized {
+        fn encode(&self, _tag: &RawTag, _offset: u32) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_default_size() {
+        assert_eq!(<Unsized as Decode>::SIZE, 0);
+        assert_eq!(<Unsized as Encode>::SIZE, 0);
+
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        assert!(tag.get_value::<Unsized>(0).is_ok());
+        let res: Result<[Unsized; 2]> = tag.get_value(0);
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+        let res = tag.set_value(0, (1_u8, Unsized));
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    #[test]
+    fn test_duration() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        tag.set_value(0, Duration::from_millis(1500)).unwrap();
+        assert_eq!(tag.get_u32(0).unwrap(), 1500);
+        let value: Duration = tag.get_value(0).unwrap();
+        assert_eq!(value, Duration::from_millis(1500));
+
+        let res = tag.set_value(0, Duration::from_secs(u64::MAX));
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_TOO_LARGE);
+    }
+
+    #[test]
+    fn test_char() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        tag.set_value(0, 'A').unwrap();
+        assert_eq!(tag.get_u8(0).unwrap(), b'A');
+        let value: char = tag.get_value(0).unwrap();
+        assert_eq!(value, 'A');
+
+        let res = tag.set_value(0, '€');
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_ENCODE);
+    }
+
+    #[test]
+    fn test_str() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let attrs = tag.string_attrs();
+        attrs.set_counted(true).unwrap();
+        attrs.set_count_word_bytes(1).unwrap();
+        attrs.set_max_capacity(20).unwrap();
+        attrs.set_total_length(21).unwrap();
+
+        tag.set_value(0, "hello").unwrap();
+        let value: String = tag.get_value(0).unwrap();
+        assert_eq!(value, "hello");
+
+        let res = tag.set_value(0, "hel\0lo");
+        assert_eq!(res.unwrap_err().code(), ffi::PLCTAG_ERR_BAD_PARAM);
+    }
+
+    #[cfg(feature = "f16")]
+    #[test]
+    fn test_f16() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let value = half::f16::from_f32(-2.25);
+        tag.set_value(2, value).unwrap();
+        let res: half::f16 = tag.get_value(2).unwrap();
+        assert_eq!(res, value);
+    }
+
+    #[test]
+    fn test_bytes() {
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        assert_eq!(<Bytes<16> as Decode>::SIZE, 16);
+
+        let value = Bytes(core::array::from_fn::<u8, 16, _>(|i| i as u8 + 1));
+        tag.set_value(2, value).unwrap();
+        assert_eq!(tag.get_u8(2).unwrap(), 1);
+        assert_eq!(tag.get_u8(17).unwrap(), 16);
+        let res: Bytes<16> = tag.get_value(2).unwrap();
+        assert_eq!(res, value);
+
+        let size = tag.size().unwrap();
+        assert!(tag.get_value::<Bytes<16>>(size - 8).is_err());
+    }
 }
@@ -0,0 +1,20 @@
+// plctag-rs
+//
+// a rust wrapper of libplctag, with rust style APIs and useful extensions.
+// Copyright: 2022, Joylei <leingliu@gmail.com>
+// License: MIT
+
+// shutdown affects all tags in the process, keep it in its own test binary
+use plctag_core::{shutdown, RawTag};
+
+#[test]
+fn test_shutdown() {
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    assert!(tag.read(100).is_ok());
+    drop(tag);
+
+    shutdown();
+
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    assert!(tag.read(100).is_ok());
+}
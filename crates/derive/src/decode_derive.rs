@@ -4,7 +4,7 @@
 // Copyright: 2022, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use crate::shared::{get_crate, get_fields, Context};
+use crate::shared::{array_elem, get_crate, get_fields, tag_size, Context};
 use proc_macro2::TokenStream;
 use proc_quote::quote;
 use syn::{DeriveInput, Index};
@@ -21,10 +21,54 @@ pub fn expand_tag_derive(input: DeriveInput) -> syn::Result<TokenStream> {
                 Some(ref f) => quote! {
                     res.#field_name =  #f(tag, offset)?;
                 },
+                None if attr.bit.is_some() => {
+                    let index = attr.offset_tokens().unwrap();
+                    let bit = Index::from(attr.bit.unwrap() as usize);
+                    quote! {
+                        {
+                            let bit_offset = offset
+                                .checked_add(#index)
+                                .and_then(|v| v.checked_mul(8))
+                                .and_then(|v| v.checked_add(#bit))
+                                .ok_or(#plctag::Status::Err(
+                                    #plctag::ffi::PLCTAG_ERR_OUT_OF_BOUNDS,
+                                ))?;
+                            res.#field_name = tag.get_bit(bit_offset)?;
+                        }
+                    }
+                }
+                None if attr.string => {
+                    let index = attr.offset_tokens().unwrap();
+                    quote! {
+                        res.#field_name = tag.get_string_owned(offset + #index)?;
+                    }
+                }
+                None if attr.len.is_some() => {
+                    let index = attr.offset_tokens().unwrap();
+                    let len = Index::from(attr.len.unwrap() as usize);
+                    let elem = array_elem(ty).unwrap();
+                    quote! {
+                        // variable sized elements would overlap each other
+                        if <#elem as #plctag::Decode>::SIZE == 0 {
+                            return Err(#plctag::Status::Err(#plctag::ffi::PLCTAG_ERR_BAD_PARAM));
+                        }
+                        for i in 0..#len {
+                            let elem_offset = (i as u32)
+                                .checked_mul(<#elem as #plctag::Decode>::SIZE)
+                                .and_then(|v| v.checked_add(#index))
+                                .and_then(|v| v.checked_add(offset))
+                                .ok_or(#plctag::Status::Err(
+                                    #plctag::ffi::PLCTAG_ERR_OUT_OF_BOUNDS,
+                                ))?;
+                            res.#field_name[i] = <#elem as #plctag::Decode>::decode(tag, elem_offset)?;
+                        }
+                    }
+                }
                 None => {
-                    let index = Index::from(attr.offset.unwrap() as usize);
+                    // any type implementing `Decode`, including nested UDTs
+                    let index = attr.offset_tokens().unwrap();
                     quote! {
-                        res.#field_name = #ty::decode(tag, offset + #index)?;
+                        res.#field_name = <#ty as #plctag::Decode>::decode(tag, offset + #index)?;
                     }
                 }
             };
@@ -32,12 +76,16 @@ pub fn expand_tag_derive(input: DeriveInput) -> syn::Result<TokenStream> {
         })
         .collect::<syn::Result<TokenStream>>()?;
 
+    let size = tag_size(&items, &plctag, &ctx);
+
     let st_name = input.ident;
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     Ok(quote! {
         impl  #impl_generics #plctag::Decode for #st_name #ty_generics #where_clause
          {
+            const SIZE: u32 = Self::TAG_SIZE;
+
             fn decode(tag: &#plctag::RawTag, offset: u32) -> #plctag::Result<Self>{
                 use #plctag::Decode;
 
@@ -46,5 +94,10 @@ pub fn expand_tag_derive(input: DeriveInput) -> syn::Result<TokenStream> {
                 Ok(res)
             }
         }
+
+        impl #impl_generics #st_name #ty_generics #where_clause {
+            /// byte size of the UDT, max field offset plus field size
+            pub const TAG_SIZE: u32 = #size;
+        }
     })
 }
@@ -4,7 +4,7 @@
 // Copyright: 2022, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use crate::shared::{get_crate, get_fields, Context};
+use crate::shared::{array_elem, get_crate, get_fields, tag_size, Context};
 use proc_macro2::TokenStream;
 use proc_quote::quote;
 use syn::{DeriveInput, Index};
@@ -16,13 +16,56 @@ pub fn expand_tag_derive(input: DeriveInput) -> syn::Result<TokenStream> {
 
     let sets = items
         .iter()
-        .map(|(field_name, _ty, attr)| {
+        .map(|(field_name, ty, attr)| {
             let ts = match attr.encode_fn {
                 Some(ref f) => quote! {
                     #f(&self.#field_name, tag, offset)?;
                 },
+                None if attr.bit.is_some() => {
+                    let index = attr.offset_tokens().unwrap();
+                    let bit = Index::from(attr.bit.unwrap() as usize);
+                    quote! {
+                        {
+                            let bit_offset = offset
+                                .checked_add(#index)
+                                .and_then(|v| v.checked_mul(8))
+                                .and_then(|v| v.checked_add(#bit))
+                                .ok_or(#plctag::Status::Err(
+                                    #plctag::ffi::PLCTAG_ERR_OUT_OF_BOUNDS,
+                                ))?;
+                            tag.set_bit(bit_offset, self.#field_name)?;
+                        }
+                    }
+                }
+                None if attr.string => {
+                    let index = attr.offset_tokens().unwrap();
+                    quote! {
+                        tag.try_set_string(offset + #index, self.#field_name.as_str())?;
+                    }
+                }
+                None if attr.len.is_some() => {
+                    let index = attr.offset_tokens().unwrap();
+                    let len = Index::from(attr.len.unwrap() as usize);
+                    let elem = array_elem(ty).unwrap();
+                    quote! {
+                        // variable sized elements would overlap each other
+                        if <#elem as #plctag::Encode>::SIZE == 0 {
+                            return Err(#plctag::Status::Err(#plctag::ffi::PLCTAG_ERR_BAD_PARAM));
+                        }
+                        for i in 0..#len {
+                            let elem_offset = (i as u32)
+                                .checked_mul(<#elem as #plctag::Encode>::SIZE)
+                                .and_then(|v| v.checked_add(#index))
+                                .and_then(|v| v.checked_add(offset))
+                                .ok_or(#plctag::Status::Err(
+                                    #plctag::ffi::PLCTAG_ERR_OUT_OF_BOUNDS,
+                                ))?;
+                            #plctag::Encode::encode(&self.#field_name[i], tag, elem_offset)?;
+                        }
+                    }
+                }
                 None => {
-                    let index = Index::from(attr.offset.unwrap() as usize);
+                    let index = attr.offset_tokens().unwrap();
                     quote! {
                         #plctag::Encode::encode(&self.#field_name, tag, offset + #index)?;
                     }
@@ -32,11 +75,14 @@ pub fn expand_tag_derive(input: DeriveInput) -> syn::Result<TokenStream> {
         })
         .collect::<syn::Result<TokenStream>>()?;
 
+    let size = tag_size(&items, &plctag, &ctx);
     let st_name = input.ident;
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     Ok(quote! {
         impl  #impl_generics #plctag::Encode for #st_name #ty_generics #where_clause{
+            const SIZE: u32 = #size;
+
             fn encode(&self, tag: &#plctag::RawTag, offset: u32) -> #plctag::Result<()>{
                 #sets
                 Ok(())
@@ -91,6 +91,86 @@ use syn::parse_macro_input;
 ///     tag.get_u32(offset + 8).map(|v|v+1)
 /// }
 /// ```
+///
+/// # Offset
+/// `offset` is a `u32` const expression, i.e. `#[tag(offset=4)]`, `#[tag(offset=A_OFF)]`
+/// or `#[tag(offset=A_OFF + 4)]`, to centralize layout constants.
+///
+/// # Skip
+/// `#[tag(skip)]` ignores the field, which is filled with `Default::default()` when decoding.
+///
+/// # Bit
+/// `#[tag(offset=N, bit=M)]` reads bit `M` in range [0-7] from byte offset `N` into `bool` with `RawTag::get_bit`.
+///
+/// ```rust,no_run
+/// use plctag_derive::{Decode, Encode};
+///
+/// #[derive(Debug, Default, Decode, Encode)]
+/// struct Status {
+///    #[tag(offset=4, bit=0)]
+///    running: bool,
+///    #[tag(offset=4, bit=3)]
+///    faulted: bool,
+/// }
+/// ```
+///
+/// # Array
+/// `#[tag(offset=N, len=L)]` reads `L` elements into an array field starting at byte offset `N`,
+/// elements are `Decode::SIZE` bytes apart, i.e. `DINT[8]` maps to `[i32; 8]`.
+///
+/// ```rust,no_run
+/// use plctag_derive::{Decode, Encode};
+///
+/// #[derive(Debug, Default, Decode, Encode)]
+/// struct MyUDT {
+///    #[tag(offset=0, len=8)]
+///    values: [i32; 8],
+/// }
+/// ```
+///
+/// # Size
+/// the macro also generates an inherent `TAG_SIZE` const, the max field offset plus field size
+/// in bytes. Field size is `#[tag(size=S)]` if specified, otherwise `Decode::SIZE` of the field
+/// type. `TAG_SIZE` is 0 (unknown) if the size of any field is unknown: strings, fields decoded
+/// with `decode_fn` and fields of types with `Decode::SIZE` 0, unless `size` is specified.
+/// `Decode::SIZE` and `Encode::SIZE` of the UDT are the same size, so nested UDTs and arrays of
+/// UDTs follow the PLC layout instead of `size_of::<Self>()`.
+///
+/// ```rust,no_run
+/// use plctag_derive::Decode;
+///
+/// #[derive(Debug, Default, Decode)]
+/// struct MyUDT {
+///    #[tag(offset=0)]
+///    a: u32,
+///    #[tag(offset=4)]
+///    b: u16,
+/// }
+///
+/// assert_eq!(MyUDT::TAG_SIZE, 6);
+/// ```
+///
+/// # String
+/// `#[tag(string, offset=N)]` reads a length prefixed string into `String` with `RawTag::get_string_owned`,
+/// according to the string attributes of the tag.
+///
+/// ```rust,no_run
+/// use plctag_core::{RawTag, ValueExt};
+/// use plctag_derive::{Decode, Encode};
+///
+/// #[derive(Debug, Default, Decode, Encode)]
+/// struct MyUDT {
+///    #[tag(string, offset=0)]
+///    name: String,
+/// }
+///
+/// let path = "protocol=ab-eip&plc=controllogix&path=1,0&gateway=192.168.1.120&name=MyUDT1&elem_count=1";
+/// let tag = RawTag::new(path, 100).unwrap();
+/// let res = tag.read(100);
+/// assert!(res.is_ok());
+/// let udt: MyUDT = tag.get_value(0).unwrap();
+/// println!("name: {}", udt.name);
+/// ```
 #[proc_macro_derive(Decode, attributes(tag))]
 pub fn decode_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -4,9 +4,12 @@
 // Copyright: 2022, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream};
 use proc_macro_crate::{crate_name, FoundCrate};
-use syn::{Attribute, Data, DataStruct, Fields, Ident, LitInt, LitStr, Type};
+use proc_quote::quote;
+use syn::{
+    Attribute, Data, DataStruct, Expr, ExprLit, Fields, Ident, Index, Lit, LitInt, LitStr, Type,
+};
 
 pub fn get_crate() -> syn::Result<Ident> {
     let plctag = match crate_name("plctag").or_else(|_| crate_name("plctag-core")) {
@@ -35,10 +38,15 @@ pub fn get_fields(data: Data, ctx: &Context) -> syn::Result<Vec<(Ident, Type, Ta
                 .iter()
                 .filter(|attr| attr.path().is_ident("tag"))
                 .collect();
-            assert!(!attrs.is_empty());
             let offset = match attrs.len() {
-                0 => return Ok(None),
-                1 => get_tag_attr(attrs[0], ctx)?,
+                0 => return Err(missing_offset(&f.ident, ctx)),
+                1 => {
+                    let attr = get_tag_attr(attrs[0])?;
+                    if attr.skip {
+                        return Ok(None);
+                    }
+                    attr
+                }
                 _ => {
                     let mut error =
                         syn::Error::new_spanned(attrs[1], "redundant `tag()` attribute");
@@ -46,8 +54,17 @@ pub fn get_fields(data: Data, ctx: &Context) -> syn::Result<Vec<(Ident, Type, Ta
                     return Err(error);
                 }
             };
+            if offset.offset.is_none()
+                && ((ctx.is_encode && offset.encode_fn.is_none())
+                    || (!ctx.is_encode && offset.decode_fn.is_none()))
+            {
+                return Err(missing_offset(&f.ident, ctx));
+            }
             let field_name = f.ident.unwrap();
             let ty = f.ty;
+            if let Some(len) = offset.len {
+                check_array_len(&ty, len)?;
+            }
             Ok(Some((field_name, ty, offset)))
         })
         .filter_map(|res| match res {
@@ -63,25 +80,170 @@ pub fn get_fields(data: Data, ctx: &Context) -> syn::Result<Vec<(Ident, Type, Ta
     Ok(items)
 }
 
-fn get_tag_attr(attr: &Attribute, ctx: &Context) -> syn::Result<TagAttr> {
+/// field requires `offset`, or `encode_fn`/`decode_fn` depending on the derive
+fn missing_offset(field: &Option<Ident>, ctx: &Context) -> syn::Error {
+    let field = field.as_ref().unwrap();
+    let fn_name = if ctx.is_encode {
+        "encode_fn"
+    } else {
+        "decode_fn"
+    };
+    syn::Error::new_spanned(
+        field,
+        format!(
+            "field `{}` requires #[tag(offset=...)] or #[tag({}=...)]",
+            field, fn_name
+        ),
+    )
+}
+
+/// element type of array field
+pub fn array_elem(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Array(arr) => Some(&arr.elem),
+        _ => None,
+    }
+}
+
+/// byte size of the UDT as a const expression, max end of fields;
+/// `size` takes precedence, 0 (unknown) if the end of any field is unknown: strings,
+/// fields with `encode_fn`/`decode_fn` or without offset, unless sized by `size`,
+/// and fields of types with unknown `SIZE`
+pub fn tag_size(items: &[(Ident, Type, TagAttr)], plctag: &Ident, ctx: &Context) -> TokenStream {
+    let tr = if ctx.is_encode {
+        quote! { #plctag::Encode }
+    } else {
+        quote! { #plctag::Decode }
+    };
+    let mut ends = TokenStream::new();
+    for (_, ty, attr) in items {
+        let custom_fn = if ctx.is_encode {
+            attr.encode_fn.is_some()
+        } else {
+            attr.decode_fn.is_some()
+        };
+        let index = match attr.offset_tokens() {
+            Some(index) => index,
+            None => return quote! { 0_u32 },
+        };
+        let size = match (attr.size, attr.len) {
+            (Some(size), _) => {
+                let size = Index::from(size as usize);
+                quote! { #size }
+            }
+            _ if attr.bit.is_some() => quote! { 1 },
+            _ if attr.string || custom_fn => return quote! { 0_u32 },
+            (None, Some(len)) => {
+                let len = Index::from(len as usize);
+                let elem = array_elem(ty).unwrap();
+                quote! { #len * <#elem as #tr>::SIZE }
+            }
+            (None, None) => quote! { <#ty as #tr>::SIZE },
+        };
+        ends.extend(quote! {
+            let field_size = #size;
+            if field_size == 0 {
+                unknown = true;
+            }
+            let end = #index + field_size;
+            if end > size {
+                size = end;
+            }
+        });
+    }
+    quote! {
+        {
+            let mut size = 0_u32;
+            let mut unknown = false;
+            #ends
+            if unknown {
+                0
+            } else {
+                size
+            }
+        }
+    }
+}
+
+/// `len` requires an array field, not shorter than `len`
+fn check_array_len(ty: &Type, len: u32) -> syn::Result<()> {
+    let arr = match ty {
+        Type::Array(arr) => arr,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "tag attribute `len` requires an array field",
+            ))
+        }
+    };
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Int(ref lit),
+        ..
+    }) = arr.len
+    {
+        if lit.base10_parse::<u32>()? < len {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "tag attribute `len` exceeds the array length",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn get_tag_attr(attr: &Attribute) -> syn::Result<TagAttr> {
     let mut offset = None;
     let mut size = None;
     let mut encode_fn = None;
     let mut decode_fn = None;
+    let mut string = false;
+    let mut bit = None;
+    let mut skip = false;
+    let mut len = None;
 
     attr.parse_nested_meta(|item| {
         if item.path.is_ident("offset") {
             if offset.is_some() {
                 return Err(item.error("redundant definition for offset"));
             }
-            let lit: LitInt = item.value()?.parse()?;
-            offset = Some(lit.base10_parse()?);
+            // integer literal or const expression, i.e. `offset = 4`, `offset = A_OFF`
+            let expr: Expr = item.value()?.parse()?;
+            offset = Some(expr);
         } else if item.path.is_ident("size") {
             if size.is_some() {
                 return Err(item.error("redundant definition for size"));
             }
             let lit: LitInt = item.value()?.parse()?;
             size = Some(lit.base10_parse()?);
+        } else if item.path.is_ident("skip") {
+            if skip {
+                return Err(item.error("redundant definition for skip"));
+            }
+            skip = true;
+        } else if item.path.is_ident("bit") {
+            if bit.is_some() {
+                return Err(item.error("redundant definition for bit"));
+            }
+            let lit: LitInt = item.value()?.parse()?;
+            let value: u32 = lit.base10_parse()?;
+            if value > 7 {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "tag attribute `bit` must be in range [0-7]",
+                ));
+            }
+            bit = Some(value);
+        } else if item.path.is_ident("len") {
+            if len.is_some() {
+                return Err(item.error("redundant definition for len"));
+            }
+            let lit: LitInt = item.value()?.parse()?;
+            len = Some(lit.base10_parse()?);
+        } else if item.path.is_ident("string") {
+            if string {
+                return Err(item.error("redundant definition for string"));
+            }
+            string = true;
         } else if item.path.is_ident("encode_fn") {
             if encode_fn.is_some() {
                 return Err(item.error("redundant definition for encode_fn"));
@@ -102,15 +264,56 @@ fn get_tag_attr(attr: &Attribute, ctx: &Context) -> syn::Result<TagAttr> {
         Ok(())
     })?;
 
-    if ctx.is_encode && encode_fn.is_none() && offset.is_none() {
+    if skip {
+        if offset.is_some()
+            || size.is_some()
+            || encode_fn.is_some()
+            || decode_fn.is_some()
+            || string
+            || bit.is_some()
+            || len.is_some()
+        {
+            return Err(syn::Error::new_spanned(
+                attr.path(),
+                "tag attribute `skip` conflicts with other attributes",
+            ));
+        }
+        return Ok(TagAttr {
+            offset,
+            size,
+            encode_fn,
+            decode_fn,
+            string,
+            bit,
+            skip,
+            len,
+        });
+    }
+
+    if string && (offset.is_none() || encode_fn.is_some() || decode_fn.is_some()) {
+        return Err(syn::Error::new_spanned(
+            attr.path(),
+            "tag attribute `string` requires `offset`, and conflicts with `encode_fn`, `decode_fn`",
+        ));
+    }
+
+    if bit.is_some() && (offset.is_none() || encode_fn.is_some() || decode_fn.is_some() || string) {
         return Err(syn::Error::new_spanned(
             attr.path(),
-            "at least one of tag attribute `offset`, `encode_fn` is required",
+            "tag attribute `bit` requires `offset`, and conflicts with `encode_fn`, `decode_fn`, `string`",
         ));
-    } else if !ctx.is_encode && decode_fn.is_none() && offset.is_none() {
+    }
+
+    if len.is_some()
+        && (offset.is_none()
+            || encode_fn.is_some()
+            || decode_fn.is_some()
+            || string
+            || bit.is_some())
+    {
         return Err(syn::Error::new_spanned(
             attr.path(),
-            "at least one of tag attribute `offset`, `decode_fn` is required",
+            "tag attribute `len` requires `offset`, and conflicts with `encode_fn`, `decode_fn`, `string`, `bit`",
         ));
     }
 
@@ -119,14 +322,35 @@ fn get_tag_attr(attr: &Attribute, ctx: &Context) -> syn::Result<TagAttr> {
         size,
         encode_fn,
         decode_fn,
+        string,
+        bit,
+        skip,
+        len,
     })
 }
 
 pub struct TagAttr {
-    pub offset: Option<u32>,
+    /// byte offset, `u32` expression
+    pub offset: Option<Expr>,
     pub size: Option<u32>,
     pub encode_fn: Option<syn::ExprPath>,
     pub decode_fn: Option<syn::ExprPath>,
+    /// string with length prefix, see `RawTag::get_string`
+    pub string: bool,
+    /// bit index from the byte offset, see `RawTag::get_bit`
+    pub bit: Option<u32>,
+    /// ignored field, decoded as `Default::default()`
+    pub skip: bool,
+    /// number of elements of array field, elements are `SIZE` bytes apart
+    pub len: Option<u32>,
+}
+
+impl TagAttr {
+    /// offset expression in parentheses, to be added to the base offset
+    pub fn offset_tokens(&self) -> Option<TokenStream> {
+        let expr = self.offset.as_ref()?;
+        Some(quote! { (#expr) })
+    }
 }
 
 pub struct Context {
@@ -6,6 +6,7 @@
 
 use plctag_core::{RawTag, ValueExt};
 use plctag_derive::{Decode, Encode};
+use std::marker::PhantomData;
 
 #[derive(Debug, Default, Decode, Encode)]
 struct MyUDT {
@@ -15,6 +16,64 @@ struct MyUDT {
     b: u32,
 }
 
+#[derive(Debug, Default, PartialEq, Decode, Encode)]
+struct Flags {
+    #[tag(offset = 0, bit = 0)]
+    b0: bool,
+    #[tag(offset = 0, bit = 1)]
+    b1: bool,
+    #[tag(offset = 0, bit = 2)]
+    b2: bool,
+    #[tag(offset = 0, bit = 3)]
+    b3: bool,
+    #[tag(offset = 0, bit = 4)]
+    b4: bool,
+    #[tag(offset = 0, bit = 5)]
+    b5: bool,
+    #[tag(offset = 0, bit = 6)]
+    b6: bool,
+    #[tag(offset = 0, bit = 7)]
+    b7: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Decode, Encode)]
+struct Inner {
+    #[tag(offset = 0)]
+    a: u16,
+    #[tag(offset = 2)]
+    b: u16,
+}
+
+#[derive(Debug, Default, PartialEq, Decode, Encode)]
+struct Outer {
+    #[tag(offset = 0)]
+    x: u8,
+    #[tag(offset = 4)]
+    inner: Inner,
+    #[tag(offset = 8)]
+    opt: Option<u16>,
+}
+
+#[derive(Debug, Default, PartialEq, Decode, Encode)]
+struct WithSkip {
+    #[tag(offset = 0)]
+    a: u32,
+    #[tag(skip)]
+    computed: u32,
+    #[tag(skip)]
+    marker: PhantomData<u8>,
+}
+
+#[derive(Debug, Default, PartialEq, Decode, Encode)]
+struct WithArray {
+    #[tag(offset = 0)]
+    count: u16,
+    #[tag(offset = 2, len = 8)]
+    values: [u16; 8],
+    #[tag(offset = 18)]
+    tail: u16,
+}
+
 #[test]
 fn test_derive() {
     let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
@@ -24,3 +83,255 @@ fn test_derive() {
     assert_eq!(udt.a, 4);
     assert_eq!(udt.b, 0);
 }
+
+#[test]
+fn test_derive_bits() {
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    let flags = Flags {
+        b0: true,
+        b3: true,
+        b7: true,
+        ..Default::default()
+    };
+    tag.set_value(0, &flags).unwrap();
+    assert_eq!(tag.get_u8(0).unwrap(), 0b1000_1001);
+
+    let res: Flags = tag.get_value(0).unwrap();
+    assert_eq!(res, flags);
+}
+
+#[test]
+fn test_derive_bits_overflow() {
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    // bit offset at 4Gi
+    let offset = 1 << 29;
+    let res: plctag_core::Result<Flags> = tag.get_value(offset);
+    assert_eq!(
+        res.unwrap_err().code(),
+        plctag_core::ffi::PLCTAG_ERR_OUT_OF_BOUNDS
+    );
+    let res = tag.set_value(offset, Flags::default());
+    assert_eq!(
+        res.unwrap_err().code(),
+        plctag_core::ffi::PLCTAG_ERR_OUT_OF_BOUNDS
+    );
+}
+
+#[test]
+fn test_derive_nested() {
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    let outer = Outer {
+        x: 1,
+        inner: Inner { a: 2, b: 3 },
+        opt: Some(4),
+    };
+    tag.set_value(0, &outer).unwrap();
+    assert_eq!(tag.get_u16(6).unwrap(), 3);
+
+    let res: Outer = tag.get_value(0).unwrap();
+    assert_eq!(res, outer);
+}
+
+#[test]
+fn test_derive_skip() {
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    tag.set_u32(4, 9).unwrap();
+    let value = WithSkip {
+        a: 1,
+        computed: 5,
+        marker: PhantomData,
+    };
+    tag.set_value(0, &value).unwrap();
+    assert_eq!(tag.get_u32(4).unwrap(), 9);
+
+    let res: WithSkip = tag.get_value(0).unwrap();
+    assert_eq!(res.a, 1);
+    assert_eq!(res.computed, 0);
+}
+
+#[test]
+fn test_derive_array() {
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    let value = WithArray {
+        count: 8,
+        values: [1, 2, 3, 4, 5, 6, 7, 8],
+        tail: 9,
+    };
+    tag.set_value(0, &value).unwrap();
+    for i in 0..8 {
+        assert_eq!(tag.get_u16(2 + i * 2).unwrap(), i as u16 + 1);
+    }
+    assert_eq!(tag.get_u16(18).unwrap(), 9);
+
+    let res: WithArray = tag.get_value(0).unwrap();
+    assert_eq!(res, value);
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Default, Decode)]
+struct WithString {
+    #[tag(offset = 0)]
+    id: u16,
+    #[tag(string, offset = 4, size = 88)]
+    name: String,
+    #[tag(offset = 2, bit = 1)]
+    flag: bool,
+}
+
+/// string without `size`, the end of the UDT is unknown
+#[allow(dead_code)]
+#[derive(Debug, Default, Decode, Encode)]
+struct WithUnsizedString {
+    #[tag(offset = 0)]
+    a: u32,
+    #[tag(string, offset = 4)]
+    s: String,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Default, Decode)]
+struct WithUnsizedField {
+    #[tag(offset = 0)]
+    a: u32,
+    #[tag(offset = 4)]
+    s: String,
+}
+
+#[test]
+fn test_derive_tag_size() {
+    assert_eq!(MyUDT::TAG_SIZE, 8);
+    assert_eq!(Flags::TAG_SIZE, 1);
+    assert_eq!(Outer::TAG_SIZE, 10);
+    assert_eq!(WithSkip::TAG_SIZE, 4);
+    assert_eq!(WithArray::TAG_SIZE, 20);
+    assert_eq!(WithString::TAG_SIZE, 92);
+}
+
+#[test]
+fn test_derive_tag_size_unknown() {
+    assert_eq!(WithUnsizedString::TAG_SIZE, 0);
+    assert_eq!(<WithUnsizedString as plctag_core::Decode>::SIZE, 0);
+    assert_eq!(<WithUnsizedString as plctag_core::Encode>::SIZE, 0);
+    assert_eq!(WithUnsizedField::TAG_SIZE, 0);
+}
+
+const A_OFF: u32 = 4;
+const B_OFF: u32 = A_OFF + 4;
+
+#[derive(Debug, Default, PartialEq, Decode, Encode)]
+struct WithConstOffset {
+    #[tag(offset = A_OFF)]
+    a: u32,
+    #[tag(offset = B_OFF, bit = 2)]
+    flag: bool,
+    #[tag(offset = B_OFF + 2)]
+    b: u16,
+}
+
+#[test]
+fn test_derive_const_offset() {
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    let value = WithConstOffset {
+        a: 10,
+        flag: true,
+        b: 20,
+    };
+    tag.set_value(0, &value).unwrap();
+    assert_eq!(tag.get_u32(4).unwrap(), 10);
+    assert!(tag.get_bit(8 * 8 + 2).unwrap());
+    assert_eq!(tag.get_u16(10).unwrap(), 20);
+
+    let res: WithConstOffset = tag.get_value(0).unwrap();
+    assert_eq!(res, value);
+    assert_eq!(WithConstOffset::TAG_SIZE, 12);
+}
+
+/// packed layout in PLC, `size_of` would be 8 with padding
+#[derive(Debug, Default, PartialEq, Clone, Copy, Decode, Encode)]
+struct Padded {
+    #[tag(offset = 0)]
+    a: u8,
+    #[tag(offset = 1)]
+    b: u32,
+}
+
+#[test]
+fn test_derive_size_padded() {
+    assert_eq!(Padded::TAG_SIZE, 5);
+    assert_eq!(<Padded as plctag_core::Decode>::SIZE, 5);
+    assert_eq!(<Padded as plctag_core::Encode>::SIZE, 5);
+    assert_eq!(std::mem::size_of::<Padded>(), 8);
+
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    let value = [Padded { a: 1, b: 2 }, Padded { a: 3, b: 4 }];
+    tag.set_value(0, value).unwrap();
+    assert_eq!(tag.get_u8(5).unwrap(), 3);
+    assert_eq!(tag.get_u32(6).unwrap(), 4);
+
+    let res: [Padded; 2] = tag.get_value(0).unwrap();
+    assert_eq!(res, value);
+}
+
+#[derive(Debug, Default, Decode, Encode)]
+struct WithStringArray {
+    #[tag(offset = 0, len = 2)]
+    names: [String; 2],
+}
+
+#[test]
+fn test_derive_zero_sized_array() {
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    let res: plctag_core::Result<WithStringArray> = tag.get_value(0);
+    assert_eq!(
+        res.unwrap_err().code(),
+        plctag_core::ffi::PLCTAG_ERR_BAD_PARAM
+    );
+    let res = tag.set_value(0, WithStringArray::default());
+    assert_eq!(
+        res.unwrap_err().code(),
+        plctag_core::ffi::PLCTAG_ERR_BAD_PARAM
+    );
+}
+
+/// element of 1GiB, it does not touch the tag
+#[derive(Debug, Default)]
+struct Big;
+
+impl plctag_core::Decode for Big {
+    const SIZE: u32 = 1 << 30;
+
+    fn decode(_tag: &RawTag, _offset: u32) -> plctag_core::Result<Self> {
+        Ok(Big)
+    }
+}
+
+impl plctag_core::Encode for Big {
+    const SIZE: u32 = 1 << 30;
+
+    fn encode(&self, _tag: &RawTag, _offset: u32) -> plctag_core::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Decode, Encode)]
+struct WithBigArray {
+    #[tag(offset = 1, len = 2)]
+    values: [Big; 2],
+}
+
+#[test]
+fn test_derive_array_overflow() {
+    let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+    // the second element is at 4GiB
+    let offset = 3 << 30;
+    let res: plctag_core::Result<WithBigArray> = tag.get_value(offset);
+    assert_eq!(
+        res.unwrap_err().code(),
+        plctag_core::ffi::PLCTAG_ERR_OUT_OF_BOUNDS
+    );
+    let res = tag.set_value(offset, WithBigArray::default());
+    assert_eq!(
+        res.unwrap_err().code(),
+        plctag_core::ffi::PLCTAG_ERR_OUT_OF_BOUNDS
+    );
+}
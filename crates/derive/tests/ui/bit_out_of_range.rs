@@ -0,0 +1,10 @@
+use plctag_derive::Decode;
+
+#[allow(dead_code)]
+#[derive(Debug, Default, Decode)]
+struct Flags {
+    #[tag(offset = 0, bit = 8)]
+    b8: bool,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use plctag_derive::Decode;
+
+#[allow(dead_code)]
+#[derive(Debug, Default, Decode)]
+struct MyUDT {
+    #[tag(offset = 0)]
+    a: u32,
+    #[tag(size = 4)]
+    b: u32,
+}
+
+fn main() {}
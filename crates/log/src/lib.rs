@@ -58,8 +58,14 @@ extern crate log;
 pub use plctag_core::builder::DebugLevel;
 
 use plctag_core::ffi;
+use std::borrow::Cow;
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, Once};
+
+/// messages above this level are dropped by [`log_route`] and `log_route_structured`
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(u8::MAX);
 
 /// set debug level of `libplctag`
 ///
@@ -92,8 +98,17 @@ fn get_int_attr(attr: &str) -> i32 {
 }
 
 #[doc(hidden)]
-unsafe extern "C" fn log_route(_tag_id: i32, level: i32, message: *const c_char) {
+unsafe extern "C" fn log_route(tag_id: i32, level: i32, message: *const c_char) {
+    if level > MAX_LEVEL.load(Ordering::Relaxed) as i32 {
+        return;
+    }
     let msg = CStr::from_ptr(message).to_string_lossy();
+    // messages not related to any tag come with tag id 0
+    let msg = if tag_id > 0 {
+        Cow::Owned(format!("[tag {}] {}", tag_id, msg))
+    } else {
+        msg
+    };
     match level {
         1 => error!("{}", msg),
         2 => warn!("{}", msg),
@@ -105,6 +120,24 @@ unsafe extern "C" fn log_route(_tag_id: i32, level: i32, message: *const c_char)
     }
 }
 
+#[doc(hidden)]
+#[cfg(feature = "kv")]
+unsafe extern "C" fn log_route_structured(tag_id: i32, level: i32, message: *const c_char) {
+    if level > MAX_LEVEL.load(Ordering::Relaxed) as i32 {
+        return;
+    }
+    let msg = CStr::from_ptr(message).to_string_lossy();
+    match level {
+        1 => error!(tag_id = tag_id; "{}", msg),
+        2 => warn!(tag_id = tag_id; "{}", msg),
+        3 => info!(tag_id = tag_id; "{}", msg),
+        4 => debug!(tag_id = tag_id; "{}", msg),
+        5 => trace!(tag_id = tag_id; "{}", msg),
+        6 => trace!(tag_id = tag_id; "{}", msg),
+        _ => (),
+    }
+}
+
 /// by default, `libplctag` logs internal messages to stdout, if you set debug level other than none.
 /// you can register your own logger by calling [`register_logger`].
 /// For convenient, this method will register a logger for you and will forward internal log messages to crate`log`.
@@ -123,9 +156,50 @@ unsafe extern "C" fn log_route(_tag_id: i32, level: i32, message: *const c_char)
 ///
 /// ```
 pub fn log_adapt() {
+    MAX_LEVEL.store(u8::MAX, Ordering::Relaxed);
+    register_route(log_route);
+}
+
+/// same as [`log_adapt`], but messages less severe than `min` are dropped
+/// before they hit crate`log`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use plctag_log::*;
+///
+/// log_adapt_with_filter(DebugLevel::Warn); // only errors and warnings
+/// set_debug_level(DebugLevel::Detail);
+/// ```
+pub fn log_adapt_with_filter(min: DebugLevel) {
+    MAX_LEVEL.store(min as u8, Ordering::Relaxed);
+    register_route(log_route);
+}
+
+/// same as [`log_adapt`], but the tag id is attached as key-value `tag_id` of the log record,
+/// instead of being prefixed to the message.
+///
+/// requires feature `kv`.
+#[cfg(feature = "kv")]
+pub fn log_adapt_structured() {
+    MAX_LEVEL.store(u8::MAX, Ordering::Relaxed);
+    register_route(log_route_structured);
+}
+
+/// same as [`log_adapt`], but registers the logger only once, no matter how many times or
+/// from how many threads it's called.
+pub fn log_adapt_once() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(log_adapt);
+}
+
+/// `libplctag` has only one global logger, serialize the registrations
+#[inline]
+fn register_route(route: unsafe extern "C" fn(tag_id: i32, level: i32, message: *const c_char)) {
+    static LOCK: Mutex<()> = Mutex::new(());
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
     unsafe {
         ffi::plc_tag_unregister_logger();
-        let rc = ffi::plc_tag_register_logger(Some(log_route));
+        let rc = ffi::plc_tag_register_logger(Some(route));
         debug_assert_eq!(rc, ffi::PLCTAG_STATUS_OK as i32);
     }
 }
@@ -135,7 +209,20 @@ mod tests {
     use super::*;
     use log::*;
     use plctag_core::RawTag;
-    use std::sync::{Arc, Mutex};
+    use std::sync::{Arc, Mutex, OnceLock};
+
+    /// logger can only be installed once, shared by all tests
+    static LOGGER: OnceLock<MemLogger> = OnceLock::new();
+    /// tests touch global states, run them one by one
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    fn logger() -> &'static MemLogger {
+        LOGGER.get_or_init(|| {
+            let logger = MemLogger::new();
+            logger.init();
+            logger
+        })
+    }
 
     struct MemLogger {
         buf: Arc<Mutex<Vec<String>>>,
@@ -152,6 +239,10 @@ mod tests {
             self.buf.lock().unwrap().clone()
         }
 
+        fn clear(&self) {
+            self.buf.lock().unwrap().clear();
+        }
+
         fn init(&self) {
             log::set_max_level(LevelFilter::Trace);
             let _ = log::set_boxed_logger(Box::new(self.clone()));
@@ -171,18 +262,21 @@ mod tests {
             meta.level() <= Level::Error
         }
         fn log(&self, record: &log::Record<'_>) {
-            self.buf
-                .lock()
-                .unwrap()
-                .push(format!("{} - {}", record.target(), record.args()));
+            self.buf.lock().unwrap().push(format!(
+                "{} {} - {}",
+                record.level(),
+                record.target(),
+                record.args()
+            ));
         }
         fn flush(&self) {}
     }
 
     #[test]
     fn test_log_adapt() {
-        let logger = MemLogger::new();
-        logger.init();
+        let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let logger = logger();
+        logger.clear();
         log_adapt();
         set_debug_level(DebugLevel::Detail);
 
@@ -197,4 +291,68 @@ mod tests {
         let msg = buf.join("\r\n");
         assert!(msg.contains("plc_tag_create"));
     }
+
+    #[test]
+    fn test_log_adapt_with_filter() {
+        let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let logger = logger();
+        logger.clear();
+        log_adapt_with_filter(DebugLevel::Warn);
+        set_debug_level(DebugLevel::Detail);
+
+        // unknown system tag, creation fails with warnings
+        let res = RawTag::new("make=system&family=library&name=unknown&debug=4", 100);
+        assert!(res.is_err());
+
+        let buf = logger.buf();
+        assert!(!buf.is_empty());
+        assert!(buf
+            .iter()
+            .all(|msg| msg.starts_with("ERROR") || msg.starts_with("WARN")));
+        log_adapt();
+    }
+
+    #[test]
+    fn test_log_tag_id() {
+        let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let logger = logger();
+        logger.clear();
+        log_adapt();
+        set_debug_level(DebugLevel::Detail);
+
+        let tag = RawTag::new("make=system&family=library&name=debug&debug=4", 100).unwrap();
+        let status = tag.read(100);
+        assert!(status.is_ok());
+
+        let msg = logger.buf().join("\r\n");
+        assert!(msg.contains(&format!("[tag {}]", tag.id())));
+    }
+
+    #[test]
+    fn test_log_adapt_once() {
+        let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let logger = logger();
+        logger.clear();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    if i % 2 == 0 {
+                        log_adapt_once();
+                    } else {
+                        log_adapt();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        log_adapt_once();
+        set_debug_level(DebugLevel::Detail);
+
+        let res = RawTag::new("make=system&family=library&name=debug&debug=4", 100);
+        assert!(res.is_ok());
+        let msg = logger.buf().join("\r\n");
+        assert!(msg.contains("plc_tag_create"));
+    }
 }
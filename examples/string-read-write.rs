@@ -28,6 +28,9 @@ impl DerefMut for S4 {
 }
 
 impl Encode for S4 {
+    // DINT count + 4 SINT
+    const SIZE: u32 = 8;
+
     fn encode(&self, tag: &RawTag, offset: u32) -> Result<()> {
         const CAPACITY: u32 = 4;
         let bytes = self.0.as_bytes();
@@ -52,6 +55,8 @@ impl Encode for S4 {
 }
 
 impl Decode for S4 {
+    const SIZE: u32 = 8;
+
     fn decode(tag: &RawTag, offset: u32) -> Result<Self> {
         let mut res: Self = Default::default();
         let pos = offset;